@@ -0,0 +1,127 @@
+use crate::arr::Arr;
+use crate::mat::Mat;
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+impl<T> AbsDiffEq for Arr<T>
+where
+    T: AbsDiffEq,
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.values.len() == other.values.len()
+            && zip_all(&self.values, &other.values, |a, b| {
+                a.abs_diff_eq(b, epsilon)
+            })
+    }
+}
+
+impl<T> RelativeEq for Arr<T>
+where
+    T: RelativeEq,
+    T::Epsilon: Copy,
+{
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.values.len() == other.values.len()
+            && zip_all(&self.values, &other.values, |a, b| {
+                a.relative_eq(b, epsilon, max_relative)
+            })
+    }
+}
+
+impl<T> UlpsEq for Arr<T>
+where
+    T: UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.values.len() == other.values.len()
+            && zip_all(&self.values, &other.values, |a, b| {
+                a.ulps_eq(b, epsilon, max_ulps)
+            })
+    }
+}
+
+impl<T> AbsDiffEq for Mat<T>
+where
+    T: AbsDiffEq,
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        mat_all(self, other, |a, b| a.abs_diff_eq(b, epsilon))
+    }
+}
+
+impl<T> RelativeEq for Mat<T>
+where
+    T: RelativeEq,
+    T::Epsilon: Copy,
+{
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        mat_all(self, other, |a, b| a.relative_eq(b, epsilon, max_relative))
+    }
+}
+
+impl<T> UlpsEq for Mat<T>
+where
+    T: UlpsEq,
+    T::Epsilon: Copy,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        mat_all(self, other, |a, b| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+fn zip_all<T>(a: &[T], b: &[T], f: impl Fn(&T, &T) -> bool) -> bool {
+    a.iter().zip(b).all(|(x, y)| f(x, y))
+}
+
+/// Returns true if the matrices have the same shape and
+/// `f` holds for all elements, regardless of storage order.
+fn mat_all<T>(a: &Mat<T>, b: &Mat<T>, f: impl Fn(&T, &T) -> bool) -> bool {
+    if a.shape() != b.shape() {
+        return false;
+    }
+    if a.col_major == b.col_major {
+        return zip_all(&a.values, &b.values, f);
+    }
+    (0..a.rows).all(|r| (0..a.cols).all(|c| f(&a[(r, c)], &b[(r, c)])))
+}
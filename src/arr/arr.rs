@@ -1,17 +1,27 @@
-use crate::full::{ones, to_string, zeros};
+use crate::error::{check_len, Error};
+use crate::full::{fmt_display, fmt_grid, fmt_lower_exp, ones, zeros};
+use crate::mask::Mask;
+use crate::mat::Mat;
 use crate::slice::{
-    all, any, arange, argmax, argsort, cum_sum, diff, find, is_nan, linspace, max, mean, min,
-    nonzero, norm, prod, range, select, set_all, set_slice, std,
+    all, allclose, any, arange, argmax, argsort, argsort_total, bincount, bincount_weighted,
+    cum_sum, cumtrapz, diff, digitize, find, gradient, histogram, histogram2d, kurtosis, linspace,
+    max, mean, median, min, mode, nanargmax, nanargmin, nanmax, nanmean, nanmin, nansum, nonzero,
+    norm, percentile, prod, quantile, range, select, set_all, set_slice, simpson, skewness, std,
+    trapz, try_argmax, try_argsort, try_diff, try_max, try_mean, try_median, try_min, try_mode,
+    try_quantile, try_std, try_var, var, Spacing,
 };
-use crate::traits::{Abs, ArcCos, ArcSin, Cos, Exp, IsNaN, Ln, Round, Sin, Sqrt};
+use crate::stats::{Bins, QuantileMethod};
+use crate::traits::{Abs, ArcCos, ArcSin, Cos, Exp, Float, IsNaN, Ln, Norm, Round, Sin, Sqrt};
 
 use num_traits::{Bounded, FromPrimitive, One, Pow, ToPrimitive, Zero};
-use std::fmt::{Display, Formatter};
-use std::ops::{AddAssign, Deref, DerefMut, Div, DivAssign, MulAssign, Sub};
+use std::fmt::{Display, Formatter, LowerExp};
+use std::ops::{AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub};
 
 // pub type Array = Arr<f64>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Arr<T> {
     pub(crate) values: Vec<T>,
     // pub(crate) column: bool,
@@ -122,10 +132,33 @@ where
         set_slice(&mut self.values, ix, v);
     }
 
+    /// Returns a `Length` error if `ix.len() != v.len()`.
+    pub fn try_set(&mut self, ix: &[usize], v: &[T]) -> Result<(), Error> {
+        check_len(ix.len(), v.len())?;
+        set_slice(&mut self.values, ix, v);
+        Ok(())
+    }
+
     pub fn set_all(&mut self, ix: &[usize], v: T) {
         set_all(&mut self.values, ix, v);
     }
 
+    /// Returns the elements where `mask` is true.
+    pub fn select_mask(&self, mask: &Mask) -> Arr<T> {
+        assert_eq!(mask.len(), self.len());
+        Arr {
+            values: mask.indices().iter().map(|&i| self.values[i]).collect(),
+        }
+    }
+
+    /// Sets the elements where `mask` is true to `v`.
+    pub fn set_where(&mut self, mask: &Mask, v: T) {
+        assert_eq!(mask.len(), self.len());
+        for i in mask.indices() {
+            self.values[i] = v;
+        }
+    }
+
     pub fn sum(&self) -> T {
         self.values
             .iter()
@@ -160,13 +193,39 @@ where
         T: PartialOrd,
     {
         let ix = argsort(&self.values, reverse);
-        let values0 = self.values.clone();
-        for (i, &j) in ix.iter().enumerate() {
-            self.values[i] = values0[j];
-        }
+        self.values = select(&self.values, &ix);
         ix
     }
 
+    /// Sorts into ascending order with NaNs last and
+    /// returns the new indexes of the elements.
+    pub fn sort_total(&mut self) -> Vec<usize>
+    where
+        T: PartialOrd + IsNaN,
+    {
+        let ix = argsort_total(&self.values);
+        self.values = select(&self.values, &ix);
+        ix
+    }
+
+    /// Returns an `Unordered` error, leaving the array
+    /// unchanged, if any values cannot be compared.
+    pub fn try_sort(&mut self) -> Result<Vec<usize>, Error>
+    where
+        T: PartialOrd,
+    {
+        self.try_sort_order(false)
+    }
+
+    pub fn try_sort_order(&mut self, reverse: bool) -> Result<Vec<usize>, Error>
+    where
+        T: PartialOrd,
+    {
+        let ix = try_argsort(&self.values, reverse)?;
+        self.values = select(&self.values, &ix);
+        Ok(ix)
+    }
+
     /// Returns an array where the values are `T::ln(a[i])`.
     pub fn ln(&self) -> Arr<T>
     where
@@ -251,6 +310,84 @@ where
         argmax(&self.values)
     }
 
+    /// Returns the maximum value, or an error if the array
+    /// is empty or any values cannot be compared.
+    pub fn try_max(&self) -> Result<T, Error>
+    where
+        T: PartialOrd,
+    {
+        try_max(&self.values)
+    }
+
+    /// Returns the minimum value, or an error if the array
+    /// is empty or any values cannot be compared.
+    pub fn try_min(&self) -> Result<T, Error>
+    where
+        T: PartialOrd,
+    {
+        try_min(&self.values)
+    }
+
+    /// Returns the index of the maximum value, or an error if
+    /// the array is empty or any values cannot be compared.
+    pub fn try_argmax(&self) -> Result<usize, Error>
+    where
+        T: PartialOrd,
+    {
+        try_argmax(&self.values)
+    }
+
+    /// Returns the sum of the values that are not NaN.
+    pub fn nansum(&self) -> T
+    where
+        T: AddAssign + IsNaN,
+    {
+        nansum(&self.values)
+    }
+
+    /// Returns the mean of the values that are not NaN,
+    /// or NaN if there are none.
+    pub fn nanmean(&self) -> T
+    where
+        T: AddAssign + Div<Output = T> + FromPrimitive + IsNaN,
+    {
+        nanmean(&self.values)
+    }
+
+    /// Returns the maximum value ignoring NaNs, or NaN if all values are NaN.
+    pub fn nanmax(&self) -> T
+    where
+        T: PartialOrd + IsNaN + Div<Output = T>,
+    {
+        nanmax(&self.values)
+    }
+
+    /// Returns the minimum value ignoring NaNs, or NaN if all values are NaN.
+    pub fn nanmin(&self) -> T
+    where
+        T: PartialOrd + IsNaN + Div<Output = T>,
+    {
+        nanmin(&self.values)
+    }
+
+    /// Returns the index of the maximum value ignoring NaNs,
+    /// or `None` if all values are NaN.
+    pub fn nanargmax(&self) -> Option<usize>
+    where
+        T: PartialOrd + IsNaN,
+    {
+        nanargmax(&self.values)
+    }
+
+    /// Returns the index of the minimum value ignoring NaNs,
+    /// or `None` if all values are NaN.
+    pub fn nanargmin(&self) -> Option<usize>
+    where
+        T: PartialOrd + IsNaN,
+    {
+        nanargmin(&self.values)
+    }
+
     /// Returns the mean of all element values.
     pub fn mean(&self) -> T
     where
@@ -259,8 +396,44 @@ where
         mean(&self.values)
     }
 
-    /// Returns the standard deviation.
-    pub fn std(&self) -> T
+    /// Returns the mean, or an `Empty` error.
+    pub fn try_mean(&self) -> Result<T, Error>
+    where
+        T: Zero + Copy + AddAssign + Div<Output = T> + FromPrimitive,
+    {
+        try_mean(&self.values)
+    }
+
+    /// Returns the variance with `ddof` delta degrees of freedom.
+    pub fn var(&self, ddof: usize) -> T
+    where
+        T: Zero
+            + Copy
+            + AddAssign
+            + Div<Output = T>
+            + FromPrimitive
+            + Mul<Output = T>
+            + Sub<Output = T>,
+    {
+        var(&self.values, ddof)
+    }
+
+    /// Returns the variance, or an `Empty` error.
+    pub fn try_var(&self, ddof: usize) -> Result<T, Error>
+    where
+        T: Zero
+            + Copy
+            + AddAssign
+            + Div<Output = T>
+            + FromPrimitive
+            + Mul<Output = T>
+            + Sub<Output = T>,
+    {
+        try_var(&self.values, ddof)
+    }
+
+    /// Returns the standard deviation with `ddof` delta degrees of freedom.
+    pub fn std(&self, ddof: usize) -> T
     where
         T: Zero
             + Copy
@@ -268,10 +441,131 @@ where
             + Div<Output = T>
             + Sqrt
             + FromPrimitive
-            + Pow<usize, Output = T>
+            + Mul<Output = T>
             + Sub<Output = T>,
     {
-        std(&self.values)
+        std(&self.values, ddof)
+    }
+
+    /// Returns the standard deviation, or an `Empty` error.
+    pub fn try_std(&self, ddof: usize) -> Result<T, Error>
+    where
+        T: Zero
+            + Copy
+            + AddAssign
+            + Div<Output = T>
+            + Sqrt
+            + FromPrimitive
+            + Mul<Output = T>
+            + Sub<Output = T>,
+    {
+        try_std(&self.values, ddof)
+    }
+
+    /// Returns the median, or NaN if any values are NaN.
+    pub fn median(&self) -> T
+    where
+        T: Float,
+    {
+        median(&self.values)
+    }
+
+    /// Returns the median, or an `Empty` error.
+    pub fn try_median(&self) -> Result<T, Error>
+    where
+        T: Float,
+    {
+        try_median(&self.values)
+    }
+
+    /// Returns quantile `q`, for `q` in `[0, 1]`, or NaN if any values are NaN.
+    pub fn quantile(&self, q: f64, method: QuantileMethod) -> T
+    where
+        T: Float,
+    {
+        quantile(&self.values, q, method)
+    }
+
+    /// Returns quantile `q`, or an `Empty` error, or an `OutOfRange` error
+    /// if `q` is not in `[0, 1]`.
+    pub fn try_quantile(&self, q: f64, method: QuantileMethod) -> Result<T, Error>
+    where
+        T: Float,
+    {
+        try_quantile(&self.values, q, method)
+    }
+
+    /// Returns percentile `p`, for `p` in `[0, 100]`.
+    pub fn percentile(&self, p: f64, method: QuantileMethod) -> T
+    where
+        T: Float,
+    {
+        percentile(&self.values, p, method)
+    }
+
+    /// Returns the most frequent value, the smallest if tied.
+    pub fn mode(&self) -> T
+    where
+        T: Float + IsNaN,
+    {
+        mode(&self.values)
+    }
+
+    /// Returns the most frequent value, or an `Empty` error.
+    pub fn try_mode(&self) -> Result<T, Error>
+    where
+        T: Float + IsNaN,
+    {
+        try_mode(&self.values)
+    }
+
+    /// Returns the biased sample skewness.
+    pub fn skewness(&self) -> T
+    where
+        T: Float,
+    {
+        skewness(&self.values)
+    }
+
+    /// Returns the biased sample excess kurtosis.
+    pub fn kurtosis(&self) -> T
+    where
+        T: Float,
+    {
+        kurtosis(&self.values)
+    }
+
+    /// Returns the counts in each bin and the bin edges. See [`crate::slice::histogram`].
+    pub fn histogram(&self, bins: impl Into<Bins<T>>, range: Option<(T, T)>) -> (Vec<usize>, Arr<T>)
+    where
+        T: Float + FromPrimitive + AddAssign + DivAssign + MulAssign,
+    {
+        let (counts, edges) = histogram(&self.values, bins, range);
+        (counts, Arr { values: edges })
+    }
+
+    /// Returns the 2-D histogram of the points `(self[i], y[i])` and the
+    /// bin edges of each dimension. See [`crate::slice::histogram2d`].
+    pub fn histogram2d(
+        &self,
+        y: &[T],
+        xbins: impl Into<Bins<T>>,
+        ybins: impl Into<Bins<T>>,
+        range: Option<((T, T), (T, T))>,
+    ) -> (Mat<usize>, Arr<T>, Arr<T>)
+    where
+        T: Float + FromPrimitive + AddAssign + DivAssign + MulAssign,
+    {
+        let (counts, xedges, yedges) = histogram2d(&self.values, y, xbins, ybins, range);
+        (counts, Arr { values: xedges }, Arr { values: yedges })
+    }
+
+    /// Returns the bin index of each value. See [`crate::slice::digitize`].
+    pub fn digitize(&self, edges: &[T], right: bool) -> Vec<usize>
+    where
+        T: Float,
+    {
+        digitize(&self.values, edges, right)
     }
 
     /// Returns an array with the discrete difference of `a`.
@@ -285,6 +579,52 @@ where
         }
     }
 
+    /// Returns the discrete difference, or an `Empty` error.
+    pub fn try_diff(&self) -> Result<Arr<T>, Error>
+    where
+        T: Zero + Copy + Sub<Output = T>,
+    {
+        Ok(Arr {
+            values: try_diff(&self.values)?,
+        })
+    }
+
+    /// Integrates the values using the trapezoidal rule. See [`crate::slice::trapz`].
+    pub fn trapz(&self, spacing: Spacing<T>) -> T
+    where
+        T: Float,
+    {
+        trapz(&self.values, spacing)
+    }
+
+    /// Returns the cumulative trapezoidal integral. See [`crate::slice::cumtrapz`].
+    pub fn cumtrapz(&self, spacing: Spacing<T>, initial: Option<T>) -> Arr<T>
+    where
+        T: Float,
+    {
+        Arr {
+            values: cumtrapz(&self.values, spacing, initial),
+        }
+    }
+
+    /// Integrates the values using Simpson's rule. See [`crate::slice::simpson`].
+    pub fn simpson(&self, spacing: Spacing<T>) -> T
+    where
+        T: Float,
+    {
+        simpson(&self.values, spacing)
+    }
+
+    /// Returns the gradient of the values. See [`crate::slice::gradient`].
+    pub fn gradient(&self, spacing: Spacing<T>, edge_order: usize) -> Arr<T>
+    where
+        T: Float,
+    {
+        Arr {
+            values: gradient(&self.values, spacing, edge_order),
+        }
+    }
+
     /// Returns the 2-norm (Euclidean).
     pub fn norm2(&self) -> T
     where
@@ -293,12 +633,22 @@ where
         norm(&self.values)
     }
 
-    /// Returns an integer array with 1s where `T::is_nan(self[i])`.
-    pub fn is_nan(&self) -> Vec<usize>
+    /// Returns a mask that is true where `T::is_nan(self[i])`.
+    pub fn is_nan(&self) -> Mask
     where
         T: IsNaN,
     {
-        is_nan(&self.values)
+        self.values.iter().map(|v| v.is_nan()).collect()
+    }
+
+    /// Returns true if `self` and `other` have the same length and all
+    /// elements satisfy `norm(a[i] - b[i]) <= atol + rtol * norm(b[i])`.
+    pub fn approx_eq<F>(&self, other: &Self, rtol: F, atol: F) -> bool
+    where
+        T: PartialEq + Sub<Output = T> + Norm<F>,
+        F: Float,
+    {
+        allclose(&self.values, &other.values, rtol, atol)
     }
 
     pub fn sqrt(&self) -> Arr<T>
@@ -358,6 +708,25 @@ where
     }
 }
 
+impl Arr<usize> {
+    /// Returns the number of occurrences of each value. See
+    /// [`crate::slice::bincount`].
+    pub fn bincount(&self, min_length: usize) -> Vec<usize> {
+        bincount(&self.values, min_length)
+    }
+
+    /// Returns the sum of the `weights` of each value. See
+    /// [`crate::slice::bincount_weighted`].
+    pub fn bincount_weighted<T>(&self, weights: &[T], min_length: usize) -> Arr<T>
+    where
+        T: Zero + Copy + AddAssign,
+    {
+        Arr {
+            values: bincount_weighted(&self.values, weights, min_length),
+        }
+    }
+}
+
 impl<T> Deref for Arr<T> {
     type Target = Vec<T>;
 
@@ -374,10 +743,19 @@ impl<T> DerefMut for Arr<T> {
 
 impl<T> Display for Arr<T>
 where
-    T: Display + Copy,
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_grid(f, 1, self.len(), &self.values, false, fmt_display)
+    }
+}
+
+impl<T> LowerExp for Arr<T>
+where
+    T: LowerExp,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", to_string(1, self.len(), &self.values, false))
+        fmt_grid(f, 1, self.len(), &self.values, false, fmt_lower_exp)
     }
 }
 
@@ -393,3 +771,29 @@ macro_rules! arr {
         $crate::Arr::with_vec(vec![$($x),+])
     );
 }
+
+/// Asserts that two arrays are element-wise equal within a relative
+/// and absolute tolerance (default `1e-5` and `1e-8`), reporting the
+/// worst offending index and values on failure.
+#[macro_export]
+macro_rules! assert_arr_close {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::assert_arr_close!($a, $b, 1e-5, 1e-8)
+    };
+    ($a:expr, $b:expr, $rtol:expr, $atol:expr $(,)?) => {{
+        let (a, b) = (&$a, &$b);
+        assert_eq!(
+            a.len(),
+            b.len(),
+            "arrays differ in length: {} != {}",
+            a.len(),
+            b.len()
+        );
+        if let Some(i) = $crate::slice::worst_not_close(a, b, $rtol, $atol) {
+            panic!(
+                "arrays not close at index {}: {} != {} (rtol={}, atol={})",
+                i, a[i], b[i], $rtol, $atol
+            );
+        }
+    }};
+}
@@ -1,6 +1,10 @@
 use crate::arr::random::Rand;
-use crate::arr::Arr;
-use crate::slice::dot;
+use crate::arr::{Arr, CArr};
+use crate::assert_arr_close;
+use crate::error::Error;
+use crate::slice::{bincount, bincount_weighted, cumtrapz, dot, histogram2d, try_argsort, Spacing};
+use crate::stats::QuantileMethod;
+use num_complex::Complex64;
 
 use rand::Rng;
 
@@ -557,3 +561,309 @@ fn test_sort_order() {
         assert_eq!(b[i], a0[i], "sort, expected {} actual {}", a0[i], b[i]);
     }
 }
+
+#[test]
+fn test_display() {
+    let a = Arr::<f64>::with_vec(vec![1.0, -0.5, 100.0]);
+    assert_eq!(format!("{}", a), "   1 -0.5  100");
+    assert_eq!(format!("{:.2}", a), "  1.00  -0.50 100.00");
+    assert_eq!(format!("{:e}", a), "  1e0 -5e-1   1e2");
+
+    let b = Arr::<f64>::range(2000);
+    assert_eq!(format!("{}", b), "   0    1    2  ... 1997 1998 1999");
+}
+
+#[test]
+fn test_approx_eq() {
+    let a = Arr::<f64>::with_vec(vec![1.0, 100.0, f64::INFINITY]);
+    let b = Arr::<f64>::with_vec(vec![1.0 + 1e-9, 100.0 + 1e-4, f64::INFINITY]);
+    assert!(a.approx_eq(&b, 1e-5, 1e-8));
+    assert!(!a.approx_eq(&b, 1e-7, 1e-8));
+    assert!(!a.approx_eq(&Arr::with_vec(vec![1.0, 100.0]), 1e-5, 1e-8));
+
+    let n = Arr::<f64>::with_vec(vec![f64::NAN]);
+    assert!(!n.approx_eq(&n, 1e-5, 1e-8));
+
+    let c = Arr::<Complex64>::with_vec(vec![Complex64::new(3.0, 4.0)]);
+    let d = Arr::<Complex64>::with_vec(vec![Complex64::new(3.0, 4.0 + 1e-3)]);
+    assert!(c.approx_eq(&d, 0.0, 2e-3));
+    assert!(!c.approx_eq(&d, 0.0, 1e-4));
+
+    assert_arr_close!(a, b);
+    assert_arr_close!(c, d, 0.0, 2e-3);
+}
+
+#[test]
+#[should_panic(expected = "arrays not close at index 2: 3 != 3.5 (rtol=0.001, atol=0)")]
+fn test_assert_arr_close() {
+    let a = Arr::<f64>::with_vec(vec![1.0, 2.0, 3.0, 4.0]);
+    let b = Arr::<f64>::with_vec(vec![1.0, 2.1, 3.5, 4.0]);
+    assert_arr_close!(a, b, 1e-3, 0.0);
+}
+
+#[cfg(feature = "approx")]
+#[test]
+fn test_approx_traits() {
+    let a = Arr::<f64>::with_vec(vec![1.0, 2.0]);
+    let b = Arr::<f64>::with_vec(vec![1.0, 2.0 + 1e-12]);
+    approx::assert_relative_eq!(a, b, epsilon = 1e-10);
+    approx::assert_abs_diff_ne!(a, b, epsilon = 1e-14);
+}
+
+#[test]
+fn test_try_reductions() {
+    let empty = Arr::<f64>::new();
+    assert_eq!(empty.try_max(), Err(Error::Empty));
+    assert_eq!(empty.try_min(), Err(Error::Empty));
+    assert_eq!(empty.try_mean(), Err(Error::Empty));
+    assert!(empty.try_diff().is_err());
+
+    let a = Arr::<f64>::with_vec(vec![2.0, 5.0, -1.0]);
+    assert_eq!(a.try_max(), Ok(5.0));
+    assert_eq!(a.try_min(), Ok(-1.0));
+    assert_eq!(a.try_argmax(), Ok(1));
+    assert_eq!(a.try_mean(), Ok(2.0));
+
+    for nan in [0, 2] {
+        let mut b = a.clone();
+        b[nan] = f64::NAN;
+        assert_eq!(b.try_max(), Err(Error::Unordered));
+        assert_eq!(b.try_min(), Err(Error::Unordered));
+        assert_eq!(b.try_argmax(), Err(Error::Unordered));
+        let b0 = b.clone();
+        assert_eq!(b.try_sort(), Err(Error::Unordered));
+        assert_eq!(b.is_nan(), b0.is_nan());
+    }
+
+    let c: Vec<f64> = (0..100)
+        .map(|i| {
+            if i % 3 == 0 {
+                f64::NAN
+            } else {
+                (i * 37 % 101) as f64
+            }
+        })
+        .collect();
+    assert_eq!(try_argsort(&c, false), Err(Error::Unordered));
+    assert_eq!(try_argsort(a.values(), true), Ok(vec![1, 0, 2]));
+}
+
+#[test]
+fn test_try_lengths() {
+    let mut a = Arr::<f64>::zeros(4);
+    assert_eq!(
+        a.try_set(&[0, 1], &[1.0]),
+        Err(Error::Length {
+            expected: 2,
+            actual: 1
+        })
+    );
+    assert_eq!(a.try_set(&[0, 1, 3], &[1.0, 2.0, 3.0]), Ok(()));
+    assert_eq!(a.try_sort_order(true), Ok(vec![3, 1, 0, 2]));
+    assert_eq!(a.values(), &[3.0, 2.0, 1.0, 0.0]);
+
+    assert!(Arr::<Complex64>::try_from_parts(&[1.0], &[]).is_err());
+    assert!(Arr::<Complex64>::try_from_polar(&[1.0], &[0.0, 1.0]).is_err());
+    assert!(Arr::<Complex64>::try_from_interleaved(&[1.0, 2.0, 3.0]).is_err());
+    let c = Arr::<Complex64>::try_from_interleaved(&[1.0, 2.0]).unwrap();
+    assert_eq!(c.values(), &[Complex64::new(1.0, 2.0)]);
+}
+
+#[test]
+fn test_nan_reductions() {
+    let nan = f64::NAN;
+    let a = Arr::<f64>::with_vec(vec![nan, 2.0, -1.0, nan, 5.0]);
+    assert_eq!(a.nansum(), 6.0);
+    assert_eq!(a.nanmean(), 2.0);
+    assert_eq!(a.nanmax(), 5.0);
+    assert_eq!(a.nanmin(), -1.0);
+    assert_eq!(a.nanargmax(), Some(4));
+    assert_eq!(a.nanargmin(), Some(2));
+
+    let b = Arr::<f64>::with_vec(vec![nan, nan]);
+    assert_eq!(b.nansum(), 0.0);
+    assert!(b.nanmean().is_nan());
+    assert!(b.nanmax().is_nan());
+    assert_eq!(b.nanargmax(), None);
+    assert_eq!(Arr::<f64>::new().nanargmin(), None);
+}
+
+#[test]
+fn test_sort_total() {
+    let nan = f64::NAN;
+    let mut a = Arr::<f64>::with_vec(vec![3.0, nan, -1.0, nan, 2.0]);
+    let ix = a.sort_total();
+    assert_eq!(ix, vec![2, 4, 0, 1, 3]);
+    assert_eq!(&a[..3], &[-1.0, 2.0, 3.0]);
+    assert_eq!(a.is_nan().indices(), vec![3, 4]);
+}
+
+#[test]
+fn test_statistics() {
+    let a = Arr::with_vec(vec![3.0, 1.0, 4.0, 2.0]);
+    assert_eq!(a.var(0), 1.25);
+    assert_arr_close!(
+        Arr::with_vec(vec![a.var(1), a.std(0)]),
+        Arr::with_vec(vec![5.0 / 3.0, 1.25f64.sqrt()])
+    );
+    assert_eq!(a.median(), 2.5);
+    assert_eq!(Arr::with_vec(vec![5.0, 1.0, 3.0]).median(), 3.0);
+    assert!(Arr::with_vec(vec![1.0, f64::NAN]).median().is_nan());
+    assert_eq!(Arr::<f64>::new().try_median(), Err(Error::Empty));
+
+    use QuantileMethod::*;
+    let expected = [
+        (InvertedCdf, 2.0),
+        (AveragedInvertedCdf, 2.0),
+        (ClosestObservation, 2.0),
+        (InterpolatedInvertedCdf, 1.6),
+        (Hazen, 2.1),
+        (Weibull, 2.0),
+        (Linear, 2.2),
+        (MedianUnbiased, 2.0 + 1.0 / 15.0),
+        (NormalUnbiased, 2.075),
+        (Lower, 2.0),
+        (Higher, 3.0),
+        (Midpoint, 2.5),
+        (Nearest, 2.0),
+    ];
+    for (method, q) in expected {
+        let actual = a.quantile(0.4, method);
+        assert!(
+            (actual - q).abs() < 1e-12,
+            "{:?}: {} != {}",
+            method,
+            actual,
+            q
+        );
+    }
+    assert_eq!(a.quantile(0.5, AveragedInvertedCdf), 2.5);
+    // Exact ties round to the even 1-based order statistic.
+    assert_eq!(a.quantile(0.375, ClosestObservation), 2.0);
+    assert_eq!(a.quantile(0.625, ClosestObservation), 2.0);
+    assert_eq!(a.quantile(0.875, ClosestObservation), 4.0);
+    assert_eq!(a.try_quantile(1.5, Linear), Err(Error::OutOfRange));
+    assert_eq!(a.try_quantile(f64::NAN, Linear), Err(Error::OutOfRange));
+    assert_eq!(a.quantile(0.0, Weibull), 1.0);
+    assert_eq!(a.quantile(1.0, InvertedCdf), 4.0);
+    assert_eq!(a.percentile(40.0, Linear), a.quantile(0.4, Linear));
+
+    let b = Arr::with_vec(vec![3.0, 2.0, 5.0, 3.0, 1.0, 2.0]);
+    assert_eq!(b.mode(), 2.0);
+    assert_eq!(a.skewness(), 0.0);
+    assert!((a.kurtosis() + 1.36).abs() < 1e-12);
+    assert!(Arr::with_vec(vec![1.0, 1.0, 10.0]).skewness() > 0.0);
+}
+
+#[test]
+fn test_histogram() {
+    let a = Arr::with_vec(vec![0.0, 0.5, 1.0, 1.5, 2.0, 4.0, f64::NAN]);
+    let (counts, edges) = a.histogram(4, None);
+    assert_eq!(counts, vec![2, 2, 1, 1]);
+    assert_eq!(edges.values(), &[0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    let (counts, _) = a.histogram(2, Some((0.5, 1.5)));
+    assert_eq!(counts, vec![1, 2]);
+    let (counts, edges) = a.histogram(vec![0.0, 1.0, 10.0], None);
+    assert_eq!(counts, vec![2, 4]);
+    assert_eq!(edges.len(), 3);
+
+    let (counts, edges) = Arr::with_vec(vec![3.0, 3.0]).histogram(1, None);
+    assert_eq!(counts, vec![2]);
+    assert_eq!(edges.values(), &[2.5, 3.5]);
+
+    assert_eq!(bincount(&[0, 1, 1, 3], 0), vec![1, 2, 0, 1]);
+    assert_eq!(bincount(&[], 2), vec![0, 0]);
+    assert_eq!(
+        bincount_weighted(&[1, 1, 2], &[0.5, 1.0, 2.0], 0),
+        vec![0.0, 1.5, 2.0]
+    );
+    let x = Arr::<usize>::with_vec(vec![0, 1, 1, 3]);
+    assert_eq!(x.bincount(5), vec![1, 2, 0, 1, 0]);
+    assert_eq!(
+        x.bincount_weighted(&[1.0, 2.0, 3.0, 4.0], 0).values(),
+        &[1.0, 5.0, 0.0, 4.0]
+    );
+
+    let x = Arr::with_vec(vec![-1.0, 0.0, 0.5, 1.0, 2.5, f64::NAN]);
+    let edges = [0.0, 1.0, 2.0];
+    assert_eq!(x.digitize(&edges, false), vec![0, 1, 1, 2, 3, 3]);
+    assert_eq!(x.digitize(&edges, true), vec![0, 0, 1, 1, 3, 3]);
+    let edges = [2.0, 1.0, 0.0];
+    assert_eq!(x.digitize(&edges, false), vec![3, 2, 2, 1, 0, 3]);
+    assert_eq!(x.digitize(&edges, true), vec![3, 3, 2, 2, 0, 3]);
+
+    let (h, xe, ye) = histogram2d(
+        &[0.0, 0.5, 1.0, 1.0],
+        &[0.0, 2.0, 1.0, 2.0],
+        2,
+        vec![0.0, 1.0, 2.0],
+        None,
+    );
+    assert_eq!((h.rows(), h.cols()), (2, 2));
+    assert_eq!(h.values(), &[1, 0, 0, 3]);
+    assert_eq!(xe, vec![0.0, 0.5, 1.0]);
+    assert_eq!(ye, vec![0.0, 1.0, 2.0]);
+
+    let x = Arr::with_vec(vec![0.0, 0.5, 1.0, 1.0]);
+    let (h2, xe2, ye2) = x.histogram2d(&[0.0, 2.0, 1.0, 2.0], 2, vec![0.0, 1.0, 2.0], None);
+    assert!(h2 == h);
+    assert_eq!(xe2.vec(), xe);
+    assert_eq!(ye2.vec(), ye);
+}
+
+#[test]
+fn test_integrate() {
+    let y = Arr::with_vec(vec![1.0, 2.0, 3.0]);
+    assert_eq!(y.trapz(Spacing::Uniform(1.0)), 4.0);
+    assert_eq!(y.trapz(Spacing::Coords(&[4.0, 6.0, 8.0])), 8.0);
+    assert_eq!(
+        y.cumtrapz(Spacing::Uniform(1.0), None).vec(),
+        vec![1.5, 4.0]
+    );
+    assert_eq!(
+        y.cumtrapz(Spacing::Uniform(1.0), Some(0.0)).vec(),
+        vec![0.0, 1.5, 4.0]
+    );
+    assert_eq!(Arr::<f64>::new().trapz(Spacing::Uniform(1.0)), 0.0);
+    assert!(cumtrapz::<f64>(&[], Spacing::Uniform(1.0), Some(0.0)).is_empty());
+    assert!(Arr::<f64>::new()
+        .cumtrapz(Spacing::Uniform(1.0), Some(0.0))
+        .is_empty());
+
+    // Simpson's rule is exact for quadratics with uneven spacing and
+    // either an odd or even number of points.
+    let sq = |x: &[f64]| Arr::with_vec(x.iter().map(|x| x * x).collect::<Vec<_>>());
+    assert!((sq(&[0.0, 1.0, 2.0]).simpson(Spacing::Uniform(1.0)) - 8.0 / 3.0).abs() < 1e-12);
+    let x = [0.0, 0.3, 1.0, 1.8, 3.0];
+    assert!((sq(&x).simpson(Spacing::Coords(&x)) - 9.0).abs() < 1e-12);
+    let x = [0.0, 0.5, 2.0, 3.0];
+    assert!((sq(&x).simpson(Spacing::Coords(&x)) - 9.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_gradient() {
+    let f = Arr::with_vec(vec![1.0, 2.0, 4.0, 7.0, 11.0, 16.0]);
+    assert_eq!(
+        f.gradient(Spacing::Uniform(1.0), 1).vec(),
+        vec![1.0, 1.5, 2.5, 3.5, 4.5, 5.0]
+    );
+    assert_eq!(
+        f.gradient(Spacing::Uniform(1.0), 2).vec(),
+        vec![0.5, 1.5, 2.5, 3.5, 4.5, 5.5]
+    );
+    let x = [0.0, 1.0, 1.5, 3.5, 4.0, 6.0];
+    assert_arr_close!(
+        f.gradient(Spacing::Coords(&x), 1),
+        Arr::with_vec(vec![1.0, 3.0, 3.5, 6.7, 6.9, 2.5])
+    );
+
+    // Second order edges are exact for quadratics.
+    let y = Arr::with_vec(x.iter().map(|x| x * x - x).collect::<Vec<_>>());
+    assert_arr_close!(
+        y.gradient(Spacing::Coords(&x), 2),
+        Arr::with_vec(x.iter().map(|x| 2.0 * x - 1.0).collect::<Vec<_>>()),
+        1e-12,
+        1e-12
+    );
+}
@@ -1,16 +1,23 @@
 use crate::arr::Arr;
+use crate::error::{check_len, Error};
 use crate::traits::{Complex, Float};
 
 pub trait CArr<F, C> {
     fn from_parts(re: &[F], im: &[F]) -> Arr<C>;
+    /// Returns a `Length` error if `re` and `im` differ in length.
+    fn try_from_parts(re: &[F], im: &[F]) -> Result<Arr<C>, Error>;
     fn from_real(re: &[F]) -> Arr<C>;
     fn from_imag(im: &[F]) -> Arr<C>;
 
     /// Returns a complex array of size `a.len()/2`, where `a`
     /// has interleaved complex parts e.g. `[re0, im0, re1, im1...]`.
     fn from_interleaved(interleaved: &[F]) -> Arr<C>;
+    /// Returns a `Length` error if `interleaved` has an odd length.
+    fn try_from_interleaved(interleaved: &[F]) -> Result<Arr<C>, Error>;
 
     fn from_polar(norm: &[F], arg: &[F]) -> Arr<C>;
+    /// Returns a `Length` error if `norm` and `arg` differ in length.
+    fn try_from_polar(norm: &[F], arg: &[F]) -> Result<Arr<C>, Error>;
 
     fn real(&self) -> Arr<F>;
     fn imag(&self) -> Arr<F>;
@@ -32,15 +39,19 @@ where
     C: Complex<F>,
 {
     fn from_parts(real: &[F], imag: &[F]) -> Arr<C> {
-        assert_eq!(real.len(), imag.len());
+        Self::try_from_parts(real, imag).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        Arr {
+    fn try_from_parts(real: &[F], imag: &[F]) -> Result<Arr<C>, Error> {
+        check_len(real.len(), imag.len())?;
+
+        Ok(Arr {
             values: real
                 .iter()
                 .zip(imag)
                 .map(|(&re, &im)| C::new(re, im))
                 .collect(),
-        }
+        })
     }
 
     fn from_real(real: &[F]) -> Arr<C> {
@@ -55,27 +66,35 @@ where
     }
 
     fn from_interleaved(interleaved: &[F]) -> Arr<C> {
-        assert_eq!(interleaved.len() % 2, 0);
+        Self::try_from_interleaved(interleaved).unwrap_or_else(|err| panic!("{}", err))
+    }
 
+    fn try_from_interleaved(interleaved: &[F]) -> Result<Arr<C>, Error> {
         let n = interleaved.len() / 2;
+        check_len(2 * n, interleaved.len())?;
+
         let mut data = Vec::with_capacity(n);
         for i in 0..n {
             data.push(C::new(interleaved[2 * i], interleaved[2 * i + 1]));
         }
 
-        Arr { values: data }
+        Ok(Arr { values: data })
     }
 
     fn from_polar(r: &[F], theta: &[F]) -> Arr<C> {
-        assert_eq!(r.len(), theta.len());
+        Self::try_from_polar(r, theta).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        Arr {
+    fn try_from_polar(r: &[F], theta: &[F]) -> Result<Arr<C>, Error> {
+        check_len(r.len(), theta.len())?;
+
+        Ok(Arr {
             values: r
                 .iter()
                 .zip(theta)
                 .map(|p| C::from_polar(*p.0, *p.1))
                 .collect(),
-        }
+        })
     }
 
     fn real(&self) -> Arr<F> {
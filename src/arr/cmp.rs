@@ -0,0 +1,69 @@
+use crate::arr::Arr;
+use crate::mask::{check_operand, Mask, Operand};
+
+/// Element-wise comparisons. The right-hand side may be a scalar, which is
+/// broadcast, or an array or slice of the same length. Comparisons with NaN
+/// are false, except `ne_mask`.
+///
+/// Equality is named `eq_mask` and `ne_mask` rather than `eq` and `ne`,
+/// which would shadow `PartialEq` in method calls.
+impl<T> Arr<T>
+where
+    T: Copy,
+{
+    fn compare<R>(&self, rhs: R, f: impl Fn(&T, &T) -> bool) -> Mask
+    where
+        R: Operand<T>,
+    {
+        check_operand((1, self.len()), &rhs);
+        Mask::from_fn(self.len(), |i| f(&self.values[i], &rhs.value(i)))
+    }
+
+    /// Returns a mask that is true where `self[i] > rhs[i]`.
+    pub fn gt<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a > b)
+    }
+
+    /// Returns a mask that is true where `self[i] >= rhs[i]`.
+    pub fn ge<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a >= b)
+    }
+
+    /// Returns a mask that is true where `self[i] < rhs[i]`.
+    pub fn lt<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a < b)
+    }
+
+    /// Returns a mask that is true where `self[i] <= rhs[i]`.
+    pub fn le<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a <= b)
+    }
+
+    /// Returns a mask that is true where `self[i] == rhs[i]`.
+    pub fn eq_mask<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialEq,
+    {
+        self.compare(rhs, |a, b| a == b)
+    }
+
+    /// Returns a mask that is true where `self[i] != rhs[i]`.
+    pub fn ne_mask<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialEq,
+    {
+        self.compare(rhs, |a, b| a != b)
+    }
+}
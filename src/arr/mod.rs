@@ -1,5 +1,6 @@
 mod arr;
 mod carr;
+mod cmp;
 
 mod random;
 mod std_ops;
@@ -0,0 +1,45 @@
+use std::fmt::{Display, Formatter};
+
+/// Errors returned by the fallible (`try_*`) functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Matrix shapes, as `(rows, cols)`, are incompatible.
+    Shape {
+        a: (usize, usize),
+        b: (usize, usize),
+    },
+    /// A slice does not have the expected length.
+    Length { expected: usize, actual: usize },
+    /// The input is empty.
+    Empty,
+    /// A value, typically NaN, cannot be ordered.
+    Unordered,
+    /// An argument, such as a quantile, is outside its valid range or NaN.
+    OutOfRange,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Shape { a, b } => {
+                write!(f, "incompatible shapes {}x{} and {}x{}", a.0, a.1, b.0, b.1)
+            }
+            Error::Length { expected, actual } => {
+                write!(f, "expected length {}, found {}", expected, actual)
+            }
+            Error::Empty => write!(f, "empty input"),
+            Error::Unordered => write!(f, "values cannot be ordered (NaN)"),
+            Error::OutOfRange => write!(f, "argument out of range"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Returns a `Length` error unless `actual == expected`.
+pub(crate) fn check_len(expected: usize, actual: usize) -> Result<(), Error> {
+    if actual != expected {
+        return Err(Error::Length { expected, actual });
+    }
+    Ok(())
+}
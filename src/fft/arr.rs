@@ -0,0 +1,155 @@
+use crate::arr::Arr;
+use crate::fft::{FftNorm, FftPlanner};
+use crate::traits::Float;
+
+use num_complex::Complex;
+
+impl<F> Arr<Complex<F>>
+where
+    F: Float,
+{
+    /// Returns the discrete Fourier transform.
+    pub fn fft(&self) -> Self {
+        self.fft_norm(FftNorm::Backward)
+    }
+
+    /// Returns the inverse discrete Fourier transform.
+    pub fn ifft(&self) -> Self {
+        self.ifft_norm(FftNorm::Backward)
+    }
+
+    /// Returns the discrete Fourier transform scaled according to `norm`.
+    ///
+    /// A new plan is computed on every call, which costs about as much as
+    /// the transform itself. Use `fft_with` to reuse plans across calls.
+    pub fn fft_norm(&self, norm: FftNorm) -> Self {
+        self.fft_with(&mut FftPlanner::new(), norm)
+    }
+
+    /// Returns the inverse discrete Fourier transform scaled according to
+    /// `norm`, computing a new plan as `fft_norm` does.
+    pub fn ifft_norm(&self, norm: FftNorm) -> Self {
+        self.ifft_with(&mut FftPlanner::new(), norm)
+    }
+
+    /// Returns the `fft_norm` transform using a plan from `planner`.
+    pub fn fft_with(&self, planner: &mut FftPlanner<F>, norm: FftNorm) -> Self {
+        let mut values = self.values.clone();
+        planner
+            .plan(values.len())
+            .transform(&mut values, false, norm);
+        Arr { values }
+    }
+
+    /// Returns the `ifft_norm` transform using a plan from `planner`.
+    pub fn ifft_with(&self, planner: &mut FftPlanner<F>, norm: FftNorm) -> Self {
+        let mut values = self.values.clone();
+        planner
+            .plan(values.len())
+            .transform(&mut values, true, norm);
+        Arr { values }
+    }
+
+    /// Returns the real signal of length `n` whose `rfft` is `self`,
+    /// truncating or zero padding `self` to `n / 2 + 1` values. `n` is
+    /// typically `2 * (self.len() - 1)`.
+    pub fn irfft(&self, n: usize) -> Arr<F> {
+        self.irfft_norm(n, FftNorm::Backward)
+    }
+
+    /// Returns the inverse of `rfft` scaled according to `norm`,
+    /// computing a new plan as `fft_norm` does.
+    pub fn irfft_norm(&self, n: usize, norm: FftNorm) -> Arr<F> {
+        self.irfft_with(n, &mut FftPlanner::new(), norm)
+    }
+
+    /// Returns the `irfft_norm` transform using a plan from `planner`.
+    pub fn irfft_with(&self, n: usize, planner: &mut FftPlanner<F>, norm: FftNorm) -> Arr<F> {
+        let zero = Complex::new(F::zero(), F::zero());
+        let mut values = vec![zero; n];
+        for (k, v) in values.iter_mut().enumerate().take(n / 2 + 1) {
+            *v = self.values.get(k).copied().unwrap_or(zero);
+        }
+        for k in 1..n.div_ceil(2) {
+            values[n - k] = values[k].conj();
+        }
+        planner.plan(n).transform(&mut values, true, norm);
+        Arr {
+            values: values.iter().map(|v| v.re).collect(),
+        }
+    }
+}
+
+impl<F> Arr<F>
+where
+    F: Float,
+{
+    /// Returns the non-negative frequency terms, `n / 2 + 1` values,
+    /// of the discrete Fourier transform of a real signal.
+    pub fn rfft(&self) -> Arr<Complex<F>> {
+        self.rfft_norm(FftNorm::Backward)
+    }
+
+    /// Returns the `rfft` scaled according to `norm`, computing a new
+    /// plan as `fft_norm` does.
+    pub fn rfft_norm(&self, norm: FftNorm) -> Arr<Complex<F>> {
+        self.rfft_with(&mut FftPlanner::new(), norm)
+    }
+
+    /// Returns the `rfft_norm` transform using a plan from `planner`.
+    pub fn rfft_with(&self, planner: &mut FftPlanner<F>, norm: FftNorm) -> Arr<Complex<F>> {
+        let n = self.values.len();
+        let mut values: Vec<Complex<F>> = self
+            .values
+            .iter()
+            .map(|&re| Complex::new(re, F::zero()))
+            .collect();
+        planner.plan(n).transform(&mut values, false, norm);
+        values.truncate(n / 2 + 1);
+        Arr { values }
+    }
+
+    /// Returns the sample frequencies of an `n` point transform with
+    /// sample spacing `d`: `[0, 1, ..., (n - 1) / 2, -(n / 2), ..., -1] / (d * n)`.
+    pub fn fftfreq(n: usize, d: F) -> Self {
+        let scale = (d * F::from(n).unwrap()).recip();
+        let values = (0..n)
+            .map(|i| {
+                let k = if i < n.div_ceil(2) {
+                    F::from(i).unwrap()
+                } else {
+                    -F::from(n - i).unwrap()
+                };
+                k * scale
+            })
+            .collect();
+        Arr { values }
+    }
+
+    /// Returns the sample frequencies of `rfft`: `[0, 1, ..., n / 2] / (d * n)`.
+    pub fn rfftfreq(n: usize, d: F) -> Self {
+        let scale = (d * F::from(n).unwrap()).recip();
+        Arr {
+            values: (0..=n / 2).map(|i| F::from(i).unwrap() * scale).collect(),
+        }
+    }
+}
+
+impl<T> Arr<T>
+where
+    T: Copy,
+{
+    /// Returns `self` with the zero-frequency term moved to the centre.
+    pub fn fftshift(&self) -> Self {
+        let mut values = self.values.clone();
+        values.rotate_right(self.len() / 2);
+        Arr { values }
+    }
+
+    /// Inverts `fftshift`.
+    pub fn ifftshift(&self) -> Self {
+        let mut values = self.values.clone();
+        values.rotate_left(self.len() / 2);
+        Arr { values }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::arr::Arr;
+use crate::fft::{FftNorm, FftPlanner};
+use crate::mat::{Axis, Mat};
+
+use num_complex::Complex64;
+use rand::Rng;
+use std::f64::consts::PI;
+
+fn random(n: usize) -> Arr<Complex64> {
+    let mut rng = rand::thread_rng();
+    Arr::with_vec(
+        (0..n)
+            .map(|_| Complex64::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+            .collect(),
+    )
+}
+
+fn dft(x: &[Complex64]) -> Vec<Complex64> {
+    let n = x.len();
+    (0..n)
+        .map(|k| {
+            x.iter()
+                .enumerate()
+                .map(|(j, &v)| {
+                    v * Complex64::from_polar(1.0, -2.0 * PI * (j * k % n) as f64 / n as f64)
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn assert_close(a: &[Complex64], b: &[Complex64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).norm() <= tol, "index {}: {} != {}", i, x, y);
+    }
+}
+
+#[test]
+fn test_fft() {
+    // Powers of two, mixed radix and Bluestein (prime factors > 31).
+    let lengths = (0..=40).chain([64, 74, 97, 120, 211, 256, 1000]);
+    for n in lengths {
+        let x = random(n);
+        let y = x.fft();
+        assert_close(&y, &dft(&x), 1e-9 * (n as f64 + 1.0));
+        assert_close(&y.ifft(), &x, 1e-12 * (n as f64 + 1.0));
+    }
+}
+
+#[test]
+fn test_fft_norm() {
+    let x = random(12);
+    let n = 12f64;
+    let y = x.fft();
+    let ortho = x.fft_norm(FftNorm::Ortho);
+    let forward = x.fft_norm(FftNorm::Forward);
+    for k in 0..12 {
+        assert!((ortho[k] - y[k] / n.sqrt()).norm() < 1e-12);
+        assert!((forward[k] - y[k] / n).norm() < 1e-12);
+    }
+    assert_close(&ortho.ifft_norm(FftNorm::Ortho), &x, 1e-12);
+    assert_close(&forward.ifft_norm(FftNorm::Forward), &x, 1e-12);
+
+    let mut planner = FftPlanner::new();
+    let plan = planner.plan(12);
+    assert!(std::sync::Arc::ptr_eq(&plan, &planner.plan(12)));
+    let mut buf = x.to_vec();
+    plan.forward(&mut buf);
+    assert_close(&buf, &y, 1e-12);
+
+    assert_close(&x.fft_with(&mut planner, FftNorm::Backward), &y, 1e-12);
+    assert_close(&y.ifft_with(&mut planner, FftNorm::Backward), &x, 1e-12);
+    let re = Arr::with_vec(x.iter().map(|v| v.re).collect::<Vec<f64>>());
+    let half = re.rfft_with(&mut planner, FftNorm::Ortho);
+    assert_close(&half, &re.rfft_norm(FftNorm::Ortho), 1e-12);
+    let back = half.irfft_with(12, &mut planner, FftNorm::Ortho);
+    assert!(back
+        .iter()
+        .zip(re.iter())
+        .all(|(a, b)| (a - b).abs() < 1e-12));
+}
+
+#[test]
+fn test_rfft() {
+    let mut rng = rand::thread_rng();
+    for n in [1, 2, 7, 8, 37, 100] {
+        let x = Arr::with_vec(
+            (0..n)
+                .map(|_| rng.gen_range(-1.0..1.0))
+                .collect::<Vec<f64>>(),
+        );
+        let y = x.rfft();
+        assert_eq!(y.len(), n / 2 + 1);
+        let full: Vec<Complex64> = x.iter().map(|&v| Complex64::new(v, 0.0)).collect();
+        assert_close(&y, &dft(&full)[..n / 2 + 1], 1e-9 * n as f64);
+
+        let z = y.irfft(n);
+        assert_eq!(z.len(), n);
+        for (a, b) in z.iter().zip(x.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_fftfreq() {
+    assert_eq!(Arr::fftfreq(4, 0.5).vec(), vec![0.0, 0.5, -1.0, -0.5]);
+    assert_eq!(Arr::fftfreq(5, 1.0).vec(), vec![0.0, 0.2, 0.4, -0.4, -0.2]);
+    assert_eq!(Arr::rfftfreq(5, 1.0).vec(), vec![0.0, 0.2, 0.4]);
+
+    let a = Arr::with_vec(vec![0, 1, 2, -2, -1]);
+    assert_eq!(a.fftshift().vec(), vec![-2, -1, 0, 1, 2]);
+    assert_eq!(a.fftshift().ifftshift(), a);
+    let b = Arr::with_vec(vec![0, 1, -2, -1]);
+    assert_eq!(b.fftshift().vec(), vec![-2, -1, 0, 1]);
+    assert_eq!(b.fftshift().ifftshift(), b);
+}
+
+#[test]
+fn test_fft2() {
+    let (rows, cols) = (6, 5);
+    let x = random(rows * cols);
+    for col_major in [false, true] {
+        let m = Mat::new(rows, cols, x.to_vec(), col_major);
+        let get = |r: usize, c: usize| m[(r, c)];
+
+        let y = m.fft2();
+        assert_eq!(y.col_major(), col_major);
+        for k in 0..rows {
+            for l in 0..cols {
+                let mut expected = Complex64::new(0.0, 0.0);
+                for r in 0..rows {
+                    for c in 0..cols {
+                        let theta = -2.0
+                            * PI
+                            * ((k * r) as f64 / rows as f64 + (l * c) as f64 / cols as f64);
+                        expected += get(r, c) * Complex64::from_polar(1.0, theta);
+                    }
+                }
+                assert!((y[(k, l)] - expected).norm() < 1e-10);
+            }
+        }
+        let z = y.ifft2();
+        for r in 0..rows {
+            for c in 0..cols {
+                assert!((z[(r, c)] - get(r, c)).norm() < 1e-12);
+            }
+        }
+
+        let per_col = m.fft_axis(Axis::Rows);
+        for c in 0..cols {
+            let col = Arr::with_vec(m.col(c).copied().collect::<Vec<_>>()).fft();
+            assert_close(&per_col.col(c).copied().collect::<Vec<_>>(), &col, 1e-12);
+        }
+        let per_row = m.fft_axis_norm(Axis::Cols, FftNorm::Ortho);
+        for r in 0..rows {
+            let row = Arr::with_vec(m.row(r).copied().collect::<Vec<_>>()).fft_norm(FftNorm::Ortho);
+            assert_close(&per_row.row(r).copied().collect::<Vec<_>>(), &row, 1e-12);
+        }
+        let back = per_row.ifft_axis_norm(Axis::Cols, FftNorm::Ortho);
+        assert_close(&back.values, &m.values, 1e-12);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::fft::{FftNorm, FftPlanner};
+use crate::full::ix;
+use crate::mat::{Axis, Mat};
+use crate::traits::Float;
+
+use num_complex::Complex;
+
+impl<F> Mat<Complex<F>>
+where
+    F: Float,
+{
+    /// Returns the discrete Fourier transform of each column
+    /// (`Axis::Rows`) or row (`Axis::Cols`).
+    pub fn fft_axis(&self, axis: Axis) -> Self {
+        self.fft_axis_norm(axis, FftNorm::Backward)
+    }
+
+    /// Returns the inverse transform of each column or row.
+    pub fn ifft_axis(&self, axis: Axis) -> Self {
+        self.ifft_axis_norm(axis, FftNorm::Backward)
+    }
+
+    /// Returns the transform along `axis` scaled according to `norm`.
+    pub fn fft_axis_norm(&self, axis: Axis, norm: FftNorm) -> Self {
+        let mut m = self.clone();
+        m.transform_axis(&mut FftPlanner::new(), axis, false, norm);
+        m
+    }
+
+    /// Returns the inverse transform along `axis` scaled according to `norm`.
+    pub fn ifft_axis_norm(&self, axis: Axis, norm: FftNorm) -> Self {
+        let mut m = self.clone();
+        m.transform_axis(&mut FftPlanner::new(), axis, true, norm);
+        m
+    }
+
+    /// Returns the 2-D discrete Fourier transform.
+    pub fn fft2(&self) -> Self {
+        self.fft2_norm(FftNorm::Backward)
+    }
+
+    /// Returns the 2-D inverse discrete Fourier transform.
+    pub fn ifft2(&self) -> Self {
+        self.ifft2_norm(FftNorm::Backward)
+    }
+
+    /// Returns the 2-D transform scaled according to `norm`.
+    pub fn fft2_norm(&self, norm: FftNorm) -> Self {
+        self.transform2(false, norm)
+    }
+
+    /// Returns the 2-D inverse transform scaled according to `norm`.
+    pub fn ifft2_norm(&self, norm: FftNorm) -> Self {
+        self.transform2(true, norm)
+    }
+
+    fn transform2(&self, inverse: bool, norm: FftNorm) -> Self {
+        let mut planner = FftPlanner::new();
+        let mut m = self.clone();
+        // Transform the contiguous lanes first.
+        let axes = if self.col_major {
+            [Axis::Rows, Axis::Cols]
+        } else {
+            [Axis::Cols, Axis::Rows]
+        };
+        for axis in axes {
+            m.transform_axis(&mut planner, axis, inverse, norm);
+        }
+        m
+    }
+
+    // Transforms contiguous lanes in-place, copying the others
+    // through a buffer.
+    fn transform_axis(
+        &mut self,
+        planner: &mut FftPlanner<F>,
+        axis: Axis,
+        inverse: bool,
+        norm: FftNorm,
+    ) {
+        let (n, lanes) = match axis {
+            Axis::Rows => (self.rows, self.cols),
+            Axis::Cols => (self.cols, self.rows),
+        };
+        if n == 0 {
+            return;
+        }
+        let plan = planner.plan(n);
+        match (axis, self.col_major) {
+            (Axis::Rows, true) => {
+                for c in 0..lanes {
+                    plan.transform(self.col_slice_mut(c), inverse, norm);
+                }
+            }
+            (Axis::Cols, false) => {
+                for r in 0..lanes {
+                    plan.transform(self.row_slice_mut(r), inverse, norm);
+                }
+            }
+            _ => {
+                let (rows, cols, col_major) = (self.rows, self.cols, self.col_major);
+                let index = |lane: usize, i: usize| match axis {
+                    Axis::Rows => ix(rows, cols, i, lane, col_major),
+                    Axis::Cols => ix(rows, cols, lane, i, col_major),
+                };
+                let mut buf = Vec::with_capacity(n);
+                for lane in 0..lanes {
+                    buf.clear();
+                    buf.extend((0..n).map(|i| self.values[index(lane, i)]));
+                    plan.transform(&mut buf, inverse, norm);
+                    for (i, &v) in buf.iter().enumerate() {
+                        self.values[index(lane, i)] = v;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod arr;
+mod mat;
+mod plan;
+
+#[cfg(test)]
+mod fft_test;
+
+pub use plan::*;
@@ -0,0 +1,269 @@
+use crate::traits::Float;
+
+use num_complex::Complex;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+// Prime factors above this are handled by Bluestein's algorithm
+// rather than an O(p^2) generic butterfly.
+const MAX_RADIX: usize = 31;
+
+/// Scaling applied to transforms, following NumPy's `norm` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FftNorm {
+    /// No scaling of the forward transform, `1/n` on the inverse.
+    #[default]
+    Backward,
+    /// `1/sqrt(n)` on both directions.
+    Ortho,
+    /// `1/n` on the forward transform, no scaling of the inverse.
+    Forward,
+}
+
+impl FftNorm {
+    pub(crate) fn scale<F: Float>(self, n: usize, inverse: bool) -> Option<F> {
+        let n = F::from(n).unwrap();
+        match (self, inverse) {
+            (FftNorm::Backward, false) | (FftNorm::Forward, true) => None,
+            (FftNorm::Backward, true) | (FftNorm::Forward, false) => Some(n.recip()),
+            (FftNorm::Ortho, _) => Some(n.sqrt().recip()),
+        }
+    }
+}
+
+/// A precomputed discrete Fourier transform of a fixed length.
+/// Lengths with only small prime factors use a mixed-radix
+/// Cooley-Tukey algorithm, others use Bluestein's algorithm.
+#[derive(Debug)]
+pub struct Fft<F> {
+    len: usize,
+    algorithm: Algorithm<F>,
+}
+
+#[derive(Debug)]
+enum Algorithm<F> {
+    MixedRadix {
+        factors: Vec<usize>,
+        twiddles: Vec<Complex<F>>,
+    },
+    Bluestein {
+        inner: Arc<Fft<F>>,
+        chirp: Vec<Complex<F>>,
+        // Transform of the conjugate chirp, zero padded to `inner.len()`.
+        kernel: Vec<Complex<F>>,
+    },
+}
+
+impl<F> Fft<F>
+where
+    F: Float,
+{
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Computes the unscaled forward transform of `buf` in-place.
+    pub fn forward(&self, buf: &mut [Complex<F>]) {
+        assert_eq!(buf.len(), self.len, "buffer length must equal plan length");
+        self.process(buf);
+    }
+
+    /// Computes the unscaled inverse transform of `buf` in-place.
+    pub fn inverse(&self, buf: &mut [Complex<F>]) {
+        assert_eq!(buf.len(), self.len, "buffer length must equal plan length");
+        // ifft(x) = conj(fft(conj(x)))
+        buf.iter_mut().for_each(|v| *v = v.conj());
+        self.process(buf);
+        buf.iter_mut().for_each(|v| *v = v.conj());
+    }
+
+    /// Computes the forward or inverse transform of `buf` in-place,
+    /// scaled according to `norm`.
+    pub fn transform(&self, buf: &mut [Complex<F>], inverse: bool, norm: FftNorm) {
+        if inverse {
+            self.inverse(buf);
+        } else {
+            self.forward(buf);
+        }
+        if let Some(scale) = norm.scale::<F>(self.len, inverse) {
+            buf.iter_mut().for_each(|v| *v = v.scale(scale));
+        }
+    }
+
+    fn process(&self, buf: &mut [Complex<F>]) {
+        if self.len <= 1 {
+            return;
+        }
+        match &self.algorithm {
+            Algorithm::MixedRadix { factors, twiddles } => {
+                let input = buf.to_vec();
+                let mut scratch = vec![Complex::new(F::zero(), F::zero()); MAX_RADIX];
+                mixed_radix(buf, &input, 1, factors, twiddles, &mut scratch);
+            }
+            Algorithm::Bluestein {
+                inner,
+                chirp,
+                kernel,
+            } => {
+                let m = inner.len();
+                let mut a = vec![Complex::new(F::zero(), F::zero()); m];
+                for ((a, &x), &w) in a.iter_mut().zip(buf.iter()).zip(chirp) {
+                    *a = x * w;
+                }
+                inner.process(&mut a);
+                a.iter_mut().zip(kernel).for_each(|(a, &k)| *a = *a * k);
+                inner.inverse(&mut a);
+                let scale = F::from(m).unwrap().recip();
+                for ((x, &a), &w) in buf.iter_mut().zip(&a).zip(chirp) {
+                    *x = a * w * scale;
+                }
+            }
+        }
+    }
+}
+
+// Decimation in time: transforms `input[0], input[stride], ...` into
+// `out`, where `factors` multiply to `out.len()` and `twiddles` are the
+// roots of unity of the full transform, whose length is `out.len() * stride`.
+fn mixed_radix<F: Float>(
+    out: &mut [Complex<F>],
+    input: &[Complex<F>],
+    stride: usize,
+    factors: &[usize],
+    twiddles: &[Complex<F>],
+    scratch: &mut [Complex<F>],
+) {
+    let p = factors[0];
+    let m = out.len() / p;
+    if m == 1 {
+        for (j, v) in out.iter_mut().enumerate() {
+            *v = input[j * stride];
+        }
+    } else {
+        for j in 0..p {
+            mixed_radix(
+                &mut out[j * m..(j + 1) * m],
+                &input[j * stride..],
+                stride * p,
+                &factors[1..],
+                twiddles,
+                scratch,
+            );
+        }
+    }
+
+    let n = twiddles.len();
+    if p == 2 {
+        for k in 0..m {
+            let t = out[k + m] * twiddles[k * stride];
+            out[k + m] = out[k] - t;
+            out[k] = out[k] + t;
+        }
+        return;
+    }
+    for u in 0..m {
+        for q in 0..p {
+            scratch[q] = out[u + q * m];
+        }
+        for q1 in 0..p {
+            let k = u + q1 * m;
+            let step = stride * k % n;
+            let mut index = 0;
+            let mut acc = scratch[0];
+            for &s in &scratch[1..p] {
+                index += step;
+                if index >= n {
+                    index -= n;
+                }
+                acc = acc + s * twiddles[index];
+            }
+            out[k] = acc;
+        }
+    }
+}
+
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while n > 1 {
+        if p * p > n {
+            factors.push(n);
+            break;
+        }
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    factors
+}
+
+fn root_of_unity<F: Float>(k: usize, n: usize) -> Complex<F> {
+    let theta = -2.0 * PI * k as f64 / n as f64;
+    Complex::new(F::from(theta.cos()).unwrap(), F::from(theta.sin()).unwrap())
+}
+
+/// Creates and caches `Fft` plans so that repeated transforms of the
+/// same length reuse their twiddle factors.
+#[derive(Debug, Default)]
+pub struct FftPlanner<F> {
+    plans: HashMap<usize, Arc<Fft<F>>>,
+}
+
+impl<F> FftPlanner<F>
+where
+    F: Float,
+{
+    pub fn new() -> Self {
+        Self {
+            plans: HashMap::new(),
+        }
+    }
+
+    /// Returns a plan for transforms of length `len`.
+    pub fn plan(&mut self, len: usize) -> Arc<Fft<F>> {
+        if let Some(plan) = self.plans.get(&len) {
+            return plan.clone();
+        }
+        let factors = factorize(len);
+        let algorithm = if factors.iter().all(|&p| p <= MAX_RADIX) {
+            Algorithm::MixedRadix {
+                factors,
+                twiddles: (0..len).map(|k| root_of_unity(k, len)).collect(),
+            }
+        } else {
+            let m = (2 * len - 1).next_power_of_two();
+            let inner = self.plan(m);
+            // w[k] = exp(-i pi k^2 / n), reducing k^2 mod 2n for accuracy.
+            let chirp: Vec<Complex<F>> = (0..len)
+                .map(|k| {
+                    root_of_unity(
+                        ((k as u128 * k as u128) % (2 * len as u128)) as usize,
+                        2 * len,
+                    )
+                })
+                .collect();
+            let mut kernel = vec![Complex::new(F::zero(), F::zero()); m];
+            kernel[0] = chirp[0].conj();
+            for k in 1..len {
+                kernel[k] = chirp[k].conj();
+                kernel[m - k] = chirp[k].conj();
+            }
+            inner.process(&mut kernel);
+            Algorithm::Bluestein {
+                inner,
+                chirp,
+                kernel,
+            }
+        };
+        let plan = Arc::new(Fft { len, algorithm });
+        self.plans.insert(len, plan.clone());
+        plan
+    }
+}
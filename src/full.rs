@@ -1,7 +1,8 @@
 use num_traits::{One, Zero};
-use std::fmt::Display;
+use std::fmt::{self, Alignment, Display, Formatter, LowerExp};
 use std::ops::{Add, AddAssign, Mul};
 
+use crate::error::{check_len, Error};
 use crate::iter::dot;
 
 #[inline]
@@ -13,6 +14,16 @@ pub fn ix(n_row: usize, n_col: usize, row: usize, col: usize, col_major: bool) -
     }
 }
 
+/// Returns the row and column of the element at index `i`.
+#[inline]
+pub fn row_col(n_row: usize, n_col: usize, i: usize, col_major: bool) -> (usize, usize) {
+    if col_major {
+        (i % n_row, i / n_row)
+    } else {
+        (i / n_col, i % n_col)
+    }
+}
+
 #[inline]
 pub fn get<T>(n_row: usize, n_col: usize, a_x: &[T], row: usize, col: usize, col_major: bool) -> T
 where
@@ -88,7 +99,22 @@ pub fn mat_vec<T>(n_row: usize, n_col: usize, a_x: &[T], b: &[T], col_major: boo
 where
     T: Mul<Output = T> + Add<Output = T> + Zero + Copy,
 {
-    assert_eq!(b.len(), n_col);
+    try_mat_vec(n_row, n_col, a_x, b, col_major).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Performs matrix-vector multiplication, or returns
+/// a `Length` error if `b.len() != n_col`.
+pub fn try_mat_vec<T>(
+    n_row: usize,
+    n_col: usize,
+    a_x: &[T],
+    b: &[T],
+    col_major: bool,
+) -> Result<Vec<T>, Error>
+where
+    T: Mul<Output = T> + Add<Output = T> + Zero + Copy,
+{
+    check_len(n_col, b.len())?;
 
     let mut y = Vec::with_capacity(b.len());
     if col_major {
@@ -103,7 +129,7 @@ where
             y.push(dot(row.iter().copied(), b.iter().copied()))
         }
     }
-    y
+    Ok(y)
 }
 
 /// Performs matrix-matrix multiplication.
@@ -121,11 +147,30 @@ pub fn mat_mat<T>(
 where
     T: Mul<Output = T> + AddAssign + Zero + Copy,
 {
-    assert_eq!(
-        a_col, b_row,
-        "rows of b {} must equal columns of a {}",
-        b_row, a_col
-    );
+    try_mat_mat(a_row, a_col, a_x, b_row, b_col, b_x, col_major)
+        .unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Performs matrix-matrix multiplication, or returns
+/// a `Shape` error if `b_row != a_col`.
+pub fn try_mat_mat<T>(
+    a_row: usize,
+    a_col: usize,
+    a_x: &[T],
+    b_row: usize,
+    b_col: usize,
+    b_x: &[T],
+    col_major: bool,
+) -> Result<Vec<T>, Error>
+where
+    T: Mul<Output = T> + AddAssign + Zero + Copy,
+{
+    if a_col != b_row {
+        return Err(Error::Shape {
+            a: (a_row, a_col),
+            b: (b_row, b_col),
+        });
+    }
 
     let mut c = zeros(a_row, b_col);
 
@@ -139,26 +184,105 @@ where
             }
         }
     }
-    c
+    Ok(c)
 }
 
-pub fn to_string<T>(n_row: usize, n_col: usize, a_x: &[T], col_major: bool) -> String
-where
-    T: Copy + Display,
-{
-    use std::io::Write;
+/// Number of elements above which formatted output is truncated.
+const PRINT_THRESHOLD: usize = 1000;
+
+/// Number of leading and trailing rows and columns formatted when truncated.
+const EDGE_ITEMS: usize = 3;
+
+/// Returns the indexes to be formatted, with `None` marking elided items.
+fn shown(n: usize, truncate: bool) -> Vec<Option<usize>> {
+    if truncate && n > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Some)
+            .chain([None])
+            .chain((n - EDGE_ITEMS..n).map(Some))
+            .collect()
+    } else {
+        (0..n).map(Some).collect()
+    }
+}
+
+/// Writes the elements in rows with columns aligned. Each element is
+/// formatted by `fmt` with the precision of `f`, and padded to the width
+/// of the widest element or the width of `f`. If there are more than
+/// `PRINT_THRESHOLD` elements only the leading and trailing rows and
+/// columns are written.
+pub fn fmt_grid<T>(
+    f: &mut Formatter<'_>,
+    n_row: usize,
+    n_col: usize,
+    a_x: &[T],
+    col_major: bool,
+    fmt: impl Fn(&T, Option<usize>) -> String,
+) -> fmt::Result {
+    let truncate = n_row * n_col > PRINT_THRESHOLD;
+    let rows = shown(n_row, truncate);
+    let cols = shown(n_col, truncate);
+
+    let cells: Vec<Option<Vec<String>>> = rows
+        .iter()
+        .map(|row| {
+            row.map(|i| {
+                cols.iter()
+                    .map(|col| match col {
+                        Some(j) => fmt(get_ref(n_row, n_col, a_x, i, *j, col_major), f.precision()),
+                        None => "...".to_string(),
+                    })
+                    .collect()
+            })
+        })
+        .collect();
 
-    let mut w = Vec::<u8>::new();
-    for i in 0..n_row {
-        for j in 0..n_col {
+    let width = cells
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|s| s.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(f.width().unwrap_or(0));
+
+    for (i, row) in cells.iter().enumerate() {
+        if i != 0 {
+            writeln!(f)?;
+        }
+        let row = match row {
+            Some(row) => row,
+            None => {
+                write!(f, "...")?;
+                continue;
+            }
+        };
+        for (j, s) in row.iter().enumerate() {
             if j != 0 {
-                w.write(b" ").unwrap();
+                write!(f, " ")?;
+            }
+            match f.align() {
+                Some(Alignment::Left) => write!(f, "{:<width$}", s)?,
+                Some(Alignment::Center) => write!(f, "{:^width$}", s)?,
+                _ => write!(f, "{:>width$}", s)?,
             }
-            write!(w, "{}", get(n_row, n_col, a_x, i, j, col_major)).unwrap();
-        }
-        if i != n_row - 1 {
-            writeln!(w).unwrap();
         }
     }
-    String::from_utf8(w).unwrap()
+    Ok(())
+}
+
+/// Formats a value with `Display` and an optional precision.
+pub fn fmt_display<T: Display>(v: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, v),
+        None => format!("{}", v),
+    }
+}
+
+/// Formats a value with `LowerExp` and an optional precision.
+pub fn fmt_lower_exp<T: LowerExp>(v: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*e}", p, v),
+        None => format!("{:e}", v),
+    }
 }
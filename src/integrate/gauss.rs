@@ -0,0 +1,104 @@
+use crate::arr::Arr;
+use crate::traits::Float;
+
+use std::f64::consts::PI;
+
+/// Gauss-Legendre quadrature rule with `n` points, which integrates
+/// polynomials of degree up to `2n - 1` exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussLegendre<F> {
+    nodes: Vec<F>,
+    weights: Vec<F>,
+}
+
+impl<F> GaussLegendre<F>
+where
+    F: Float,
+{
+    /// Creates the `n` point rule, computing the nodes as the roots of the
+    /// Legendre polynomial by Newton's method.
+    ///
+    /// Panics if `n` is zero.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "rule must have at least one point");
+        let mut nodes = vec![0.0; n];
+        let mut weights = vec![0.0; n];
+        for i in 0..n.div_ceil(2) {
+            let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            for _ in 0..100 {
+                let (p, dp) = legendre(n, x);
+                let dx = p / dp;
+                x -= dx;
+                if dx.abs() <= 1e-15 {
+                    break;
+                }
+            }
+            let dp = legendre(n, x).1;
+            let w = 2.0 / ((1.0 - x * x) * dp * dp);
+            nodes[i] = -x;
+            nodes[n - 1 - i] = x;
+            weights[i] = w;
+            weights[n - 1 - i] = w;
+        }
+        Self {
+            nodes: nodes.into_iter().map(|v| F::from(v).unwrap()).collect(),
+            weights: weights.into_iter().map(|v| F::from(v).unwrap()).collect(),
+        }
+    }
+
+    /// Returns the nodes on `[-1, 1]`, in increasing order.
+    pub fn nodes(&self) -> &[F] {
+        &self.nodes
+    }
+
+    /// Returns the weights of the nodes.
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    /// Integrates `f` from `a` to `b`, both finite.
+    pub fn integrate(&self, f: impl Fn(F) -> F, a: F, b: F) -> F {
+        let (center, half) = self.scale(a, b);
+        self.nodes
+            .iter()
+            .zip(&self.weights)
+            .fold(F::zero(), |s, (&x, &w)| s + w * f(center + half * x))
+            * half
+    }
+
+    /// Integrates the vectorised function `f`, which returns its value at
+    /// each of the given points, from `a` to `b`, both finite.
+    pub fn integrate_vec(&self, f: impl Fn(&Arr<F>) -> Arr<F>, a: F, b: F) -> F {
+        let (center, half) = self.scale(a, b);
+        let x = Arr {
+            values: self.nodes.iter().map(|&x| center + half * x).collect(),
+        };
+        let y = f(&x);
+        assert_eq!(
+            y.len(),
+            x.len(),
+            "integrand must return one value per point"
+        );
+        self.weights
+            .iter()
+            .zip(y.iter())
+            .fold(F::zero(), |s, (&w, &y)| s + w * y)
+            * half
+    }
+
+    fn scale(&self, a: F, b: F) -> (F, F) {
+        let two = F::one() + F::one();
+        ((a + b) / two, (b - a) / two)
+    }
+}
+
+// Returns the Legendre polynomial of degree `n` and its derivative at `x`.
+fn legendre(n: usize, x: f64) -> (f64, f64) {
+    let (mut p0, mut p1) = (1.0, x);
+    for j in 2..=n {
+        let p2 = ((2 * j - 1) as f64 * x * p1 - (j - 1) as f64 * p0) / j as f64;
+        p0 = p1;
+        p1 = p2;
+    }
+    (p1, n as f64 * (x * p1 - p0) / (x * x - 1.0))
+}
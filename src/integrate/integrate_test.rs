@@ -0,0 +1,115 @@
+use crate::arr::Arr;
+use crate::integrate::{quad, quad_vec, tanh_sinh, GaussLegendre, QuadOptions};
+
+use std::f64::consts::PI;
+
+#[test]
+fn test_quad() {
+    let opts = QuadOptions::default();
+    let r = quad(f64::sin, 0.0, PI, opts);
+    assert!((r.value - 2.0).abs() < 1e-12);
+    assert!(r.error < 1e-8);
+    assert_eq!(r.evals, 15);
+
+    let r = quad(f64::sin, PI, 0.0, opts);
+    assert!((r.value + 2.0).abs() < 1e-12);
+    assert_eq!(quad(f64::exp, 1.0, 1.0, opts).value, 0.0);
+
+    // Needs subdivision near the peak.
+    let r = quad(|x: f64| 1.0 / (1e-4 + x * x), -1.0, 1.0, opts);
+    let exact = 2.0 * 100.0 * (100.0f64).atan();
+    assert!((r.value - exact).abs() < 1e-8 * exact);
+    assert!(r.evals > 15);
+    assert!(r.error <= 1.49e-8 * exact);
+
+    // Too few subintervals leaves the error above the tolerance.
+    let few = QuadOptions {
+        limit: 2,
+        ..QuadOptions::default()
+    };
+    let r = quad(|x: f64| 1.0 / (1e-4 + x * x), -1.0, 1.0, few);
+    assert!(r.error > 1.49e-8 * exact);
+
+    // An interval that cannot be bisected keeps its error estimate
+    // and is not evaluated again.
+    let exact_opts = QuadOptions {
+        abs_tol: 0.0,
+        rel_tol: 0.0,
+        ..QuadOptions::default()
+    };
+    let r = quad(|_| 1.0, 1.0, 1.0 + f64::EPSILON, exact_opts);
+    assert_eq!(r.value, f64::EPSILON);
+    assert!(r.error > 0.0);
+    assert_eq!(r.evals, 15);
+}
+
+#[test]
+fn test_quad_infinite() {
+    let opts = QuadOptions::default();
+    let r = quad(
+        |x: f64| (-x * x).exp(),
+        f64::NEG_INFINITY,
+        f64::INFINITY,
+        opts,
+    );
+    assert!((r.value - PI.sqrt()).abs() < 1e-10);
+    let r = quad(|x: f64| (-x).exp(), 0.0, f64::INFINITY, opts);
+    assert!((r.value - 1.0).abs() < 1e-10);
+    let r = quad(|x: f64| 1.0 / (1.0 + x * x), f64::NEG_INFINITY, 0.0, opts);
+    assert!((r.value - PI / 2.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_quad_vec() {
+    let opts = QuadOptions::default();
+    let r = quad_vec(|x: &Arr<f64>| x.exp(), 0.0, 1.0, opts);
+    assert!((r.value - (1f64.exp() - 1.0)).abs() < 1e-12);
+    let scalar = quad(|x: f64| x.cos() / (1.0 + x * x), 0.0, f64::INFINITY, opts);
+    let r = quad_vec(
+        |x: &Arr<f64>| Arr::with_vec(x.iter().map(|x| x.cos() / (1.0 + x * x)).collect()),
+        0.0,
+        f64::INFINITY,
+        opts,
+    );
+    assert_eq!(r, scalar);
+}
+
+#[test]
+fn test_tanh_sinh() {
+    let opts = QuadOptions::default();
+    let r = tanh_sinh(|x: f64| 1.0 / x.sqrt(), 0.0, 1.0, opts);
+    assert!((r.value - 2.0).abs() < 1e-8);
+    let r = tanh_sinh(|x: f64| x.ln(), 0.0, 1.0, opts);
+    assert!((r.value + 1.0).abs() < 1e-10);
+    let r = tanh_sinh(|x: f64| (1.0 - x * x).sqrt(), -1.0, 1.0, opts);
+    assert!((r.value - PI / 2.0).abs() < 1e-10);
+    assert!(r.error < 1e-8);
+    assert!(r.evals > 0);
+}
+
+#[test]
+fn test_gauss_legendre() {
+    let g = GaussLegendre::<f64>::new(3);
+    let s = 0.6f64.sqrt();
+    for (x, e) in g.nodes().iter().zip([-s, 0.0, s]) {
+        assert!((x - e).abs() < 1e-15);
+    }
+    for (w, e) in g.weights().iter().zip([5.0 / 9.0, 8.0 / 9.0, 5.0 / 9.0]) {
+        assert!((w - e).abs() < 1e-15);
+    }
+
+    // Exact for polynomials of degree 2n - 1.
+    for n in 1..=12 {
+        let g = GaussLegendre::new(n);
+        let d = 2 * n as i32 - 1;
+        let v = g.integrate(|x: f64| x.powi(d) + 1.0, 0.0, 2.0);
+        let exact = 2f64.powi(d + 1) / (d + 1) as f64 + 2.0;
+        assert!((v - exact).abs() < 1e-11 * exact, "n = {}", n);
+        let sum: f64 = g.weights().iter().sum();
+        assert!((sum - 2.0).abs() < 1e-13);
+    }
+
+    let g = GaussLegendre::new(20);
+    let v = g.integrate_vec(|x: &Arr<f64>| x.exp(), 0.0, 1.0);
+    assert!((v - (1f64.exp() - 1.0)).abs() < 1e-14);
+}
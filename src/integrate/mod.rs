@@ -0,0 +1,55 @@
+mod gauss;
+mod quad;
+mod tanh_sinh;
+
+#[cfg(test)]
+mod integrate_test;
+
+pub use gauss::*;
+pub use quad::*;
+pub use tanh_sinh::*;
+
+use crate::traits::Float;
+
+/// Result of integrating a function numerically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadResult<F> {
+    /// Estimate of the integral.
+    pub value: F,
+    /// Estimate of the absolute error of `value`.
+    pub error: F,
+    /// Number of function evaluations.
+    pub evals: usize,
+}
+
+/// Tolerances and limits for adaptive integration.
+///
+/// Integration stops once the error estimate is at most
+/// `max(abs_tol, rel_tol * |value|)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadOptions<F> {
+    pub abs_tol: F,
+    pub rel_tol: F,
+    /// Maximum number of subintervals for adaptive Gauss-Kronrod integration.
+    pub limit: usize,
+}
+
+impl<F: Float> Default for QuadOptions<F> {
+    /// Returns the SciPy `quad` defaults: tolerances of `1.49e-8` and
+    /// a limit of 50.
+    fn default() -> Self {
+        let tol = F::from(1.49e-8).unwrap();
+        Self {
+            abs_tol: tol,
+            rel_tol: tol,
+            limit: 50,
+        }
+    }
+}
+
+impl<F: Float> QuadOptions<F> {
+    // Returns true if `error` is within tolerance for `value`.
+    pub(crate) fn accepts(&self, value: F, error: F) -> bool {
+        error <= self.abs_tol.max(self.rel_tol * value.abs())
+    }
+}
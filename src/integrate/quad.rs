@@ -0,0 +1,191 @@
+use crate::arr::Arr;
+use crate::integrate::{QuadOptions, QuadResult};
+use crate::traits::Float;
+
+// Kronrod 15 point abscissae, with the 7 point Gauss abscissae at odd
+// indexes, and weights from QUADPACK.
+const XGK: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+const WGK: [f64; 8] = [
+    0.022_935_322_010_529_22,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_2,
+    0.140_653_259_715_525_9,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_8,
+];
+const WG: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// Integrates `f` from `a` to `b` using adaptive Gauss-Kronrod 7-15
+/// quadrature, repeatedly bisecting the subinterval with the largest
+/// error estimate. Either limit may be infinite, in which case the
+/// interval is mapped to a finite one.
+///
+/// If the tolerance is not met within `opts.limit` subintervals, or
+/// before the subintervals become too narrow to bisect, the best estimate
+/// is returned, with an error larger than the tolerance.
+pub fn quad<F: Float>(f: impl Fn(F) -> F, a: F, b: F, opts: QuadOptions<F>) -> QuadResult<F> {
+    integrate(|x: &[F]| x.iter().map(|&x| f(x)).collect(), a, b, opts)
+}
+
+/// Integrates the vectorised function `f`, which returns its value at
+/// each of the given points, from `a` to `b`. See [`quad`].
+pub fn quad_vec<F: Float>(
+    f: impl Fn(&Arr<F>) -> Arr<F>,
+    a: F,
+    b: F,
+    opts: QuadOptions<F>,
+) -> QuadResult<F> {
+    integrate(|x: &[F]| f(&Arr { values: x.to_vec() }).values, a, b, opts)
+}
+
+// Integrates the batch function `f` from `a` to `b`.
+fn integrate<F: Float>(
+    f: impl Fn(&[F]) -> Vec<F>,
+    a: F,
+    b: F,
+    opts: QuadOptions<F>,
+) -> QuadResult<F> {
+    if a == b {
+        return QuadResult {
+            value: F::zero(),
+            error: F::zero(),
+            evals: 0,
+        };
+    }
+    if a > b {
+        let r = integrate(f, b, a, opts);
+        return QuadResult {
+            value: -r.value,
+            ..r
+        };
+    }
+
+    let one = F::one();
+    // Substitutions mapping infinite intervals onto finite ones,
+    // returning the point and the derivative of the mapping.
+    let map = |t: F| -> (F, F) {
+        match (a.is_infinite(), b.is_infinite()) {
+            (false, false) => (t, one),
+            (false, true) => {
+                let s = one - t;
+                (a + t / s, one / (s * s))
+            }
+            (true, false) => (b - (one - t) / t, one / (t * t)),
+            (true, true) => {
+                let s = one - t * t;
+                (t / s, (one + t * t) / (s * s))
+            }
+        }
+    };
+    let (lo, hi) = match (a.is_infinite(), b.is_infinite()) {
+        (false, false) => (a, b),
+        (false, true) | (true, false) => (F::zero(), one),
+        (true, true) => (-one, one),
+    };
+    let g = |t: &[F]| -> Vec<F> {
+        let (x, dx): (Vec<F>, Vec<F>) = t.iter().map(|&t| map(t)).unzip();
+        let y = f(&x);
+        assert_eq!(
+            y.len(),
+            x.len(),
+            "integrand must return one value per point"
+        );
+        y.into_iter().zip(dx).map(|(y, dx)| y * dx).collect()
+    };
+
+    // Subintervals as (start, end, value, error), and those too narrow
+    // to divide, whose estimates are final.
+    let mut parts = vec![kronrod(&g, lo, hi)];
+    let mut done = Vec::new();
+    let mut evals = 15;
+    loop {
+        let value = parts.iter().chain(&done).fold(F::zero(), |s, p| s + p.2);
+        let error = parts.iter().chain(&done).fold(F::zero(), |s, p| s + p.3);
+        if opts.accepts(value, error)
+            || parts.len() + done.len() >= opts.limit.max(1)
+            || parts.is_empty()
+            || error.is_nan()
+        {
+            return QuadResult {
+                value,
+                error,
+                evals,
+            };
+        }
+        let worst = (0..parts.len())
+            .max_by(|&i, &j| parts[i].3.partial_cmp(&parts[j].3).unwrap())
+            .unwrap();
+        let part = parts.swap_remove(worst);
+        let (s, e, _, _) = part;
+        let m = (s + e) / (one + one);
+        if m <= s || m >= e {
+            done.push(part);
+            continue;
+        }
+        parts.push(kronrod(&g, s, m));
+        parts.push(kronrod(&g, m, e));
+        evals += 30;
+    }
+}
+
+// Applies the Gauss-Kronrod 7-15 rule to `[a, b]`, returning the interval,
+// the Kronrod estimate and its error estimate as in QUADPACK.
+fn kronrod<F: Float>(f: &impl Fn(&[F]) -> Vec<F>, a: F, b: F) -> (F, F, F, F) {
+    let c = |v: f64| F::from(v).unwrap();
+    let center = (a + b) / c(2.0);
+    let half = (b - a) / c(2.0);
+
+    let mut x = Vec::with_capacity(15);
+    for &v in &XGK[..7] {
+        x.push(center - half * c(v));
+        x.push(center + half * c(v));
+    }
+    x.push(center);
+    let y = f(&x);
+
+    let wgk = |i: usize| c(WGK[i]);
+    let mut resk = wgk(7) * y[14];
+    let mut resg = c(WG[3]) * y[14];
+    let mut resabs = wgk(7) * y[14].abs();
+    for i in 0..7 {
+        let pair = y[2 * i] + y[2 * i + 1];
+        resk = resk + wgk(i) * pair;
+        resabs = resabs + wgk(i) * (y[2 * i].abs() + y[2 * i + 1].abs());
+        if i % 2 == 1 {
+            resg = resg + c(WG[i / 2]) * pair;
+        }
+    }
+    let mean = resk / c(2.0);
+    let mut resasc = wgk(7) * (y[14] - mean).abs();
+    for i in 0..7 {
+        resasc = resasc + wgk(i) * ((y[2 * i] - mean).abs() + (y[2 * i + 1] - mean).abs());
+    }
+
+    let h = half.abs();
+    let (resabs, resasc) = (resabs * h, resasc * h);
+    let mut error = ((resk - resg) * half).abs();
+    if resasc != F::zero() && error != F::zero() {
+        error = resasc * F::one().min((c(200.0) * error / resasc).powf(c(1.5)));
+    }
+    let eps = F::epsilon();
+    if resabs > F::min_positive_value() / (c(50.0) * eps) {
+        error = error.max(c(50.0) * eps * resabs);
+    }
+    (a, b, resk * half, error)
+}
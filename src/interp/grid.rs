@@ -0,0 +1,173 @@
+use crate::arr::Arr;
+use crate::interp::{interval, CubicSpline, Extrapolate, SplineBoundary};
+use crate::mat::Mat;
+use crate::traits::Float;
+
+/// Two dimensional grid interpolation method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridMethod {
+    /// Linear along each axis within a grid cell.
+    #[default]
+    Bilinear,
+    /// Tensor product of not-a-knot cubic splines along each axis, as
+    /// SciPy's `RegularGridInterpolator` with `method="cubic"`.
+    Bicubic,
+}
+
+/// Interpolates values on a rectangular grid with increasing, possibly
+/// unevenly spaced, axes at arbitrary points.
+///
+/// `values[(i, j)]` is the value at `(x[i], y[j])`. Points outside the
+/// grid are handled according to an [`Extrapolate`] option: `Clamp`
+/// moves them to the nearest edge of the grid, `Linear` extends the
+/// interpolant of the edge cells and `Fill` returns a constant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegularGridInterpolator<F> {
+    x: Vec<F>,
+    y: Vec<F>,
+    // Coefficients `c[p][q]` of `(x - x[i])^p (y - y[j])^q` for each
+    // cell `(i, j)`, in row-major order.
+    cells: Vec<[[F; 4]; 4]>,
+    extrapolate: Extrapolate<F>,
+}
+
+impl<F> RegularGridInterpolator<F>
+where
+    F: Float,
+{
+    /// Creates an interpolator for `values` on the grid with axes `x`
+    /// along the rows and `y` along the columns.
+    ///
+    /// Panics if either axis has fewer than two values or is not strictly
+    /// increasing, or `values` is not `x.len()` by `y.len()`.
+    pub fn new(
+        x: &[F],
+        y: &[F],
+        values: &Mat<F>,
+        method: GridMethod,
+        extrapolate: Extrapolate<F>,
+    ) -> Self {
+        check_axis(x, "x");
+        check_axis(y, "y");
+        assert_eq!(
+            values.shape(),
+            (x.len(), y.len()),
+            "values must have shape (x.len(), y.len())"
+        );
+        let (nx, ny) = (x.len(), y.len());
+
+        let mut cells = vec![[[F::zero(); 4]; 4]; (nx - 1) * (ny - 1)];
+        match method {
+            GridMethod::Bilinear => {
+                for i in 0..nx - 1 {
+                    for j in 0..ny - 1 {
+                        let (hx, hy) = (x[i + 1] - x[i], y[j + 1] - y[j]);
+                        let f00 = values[(i, j)];
+                        let f10 = values[(i + 1, j)];
+                        let f01 = values[(i, j + 1)];
+                        let f11 = values[(i + 1, j + 1)];
+                        let c = &mut cells[i * (ny - 1) + j];
+                        c[0][0] = f00;
+                        c[1][0] = (f10 - f00) / hx;
+                        c[0][1] = (f01 - f00) / hy;
+                        c[1][1] = (f11 - f10 - f01 + f00) / (hx * hy);
+                    }
+                }
+            }
+            GridMethod::Bicubic => {
+                // Fit splines along each row, then along x to each of the
+                // resulting coefficients, which is exact as fitting is linear.
+                let rows: Vec<Vec<[F; 4]>> = (0..nx)
+                    .map(|i| {
+                        let v: Vec<F> = values.row(i).copied().collect();
+                        CubicSpline::new(y, &v, SplineBoundary::NotAKnot).pp.c
+                    })
+                    .collect();
+                for j in 0..ny - 1 {
+                    for q in 0..4 {
+                        let v: Vec<F> = rows.iter().map(|r| r[j][q]).collect();
+                        let s = CubicSpline::new(x, &v, SplineBoundary::NotAKnot);
+                        for (i, b) in s.pp.c.iter().enumerate() {
+                            for p in 0..4 {
+                                cells[i * (ny - 1) + j][p][q] = b[p];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            cells,
+            extrapolate,
+        }
+    }
+
+    /// Returns the interpolated value at `(x, y)`.
+    pub fn eval(&self, x: F, y: F) -> F {
+        if x.is_nan() || y.is_nan() {
+            return F::nan();
+        }
+        let (x0, x1) = (self.x[0], self.x[self.x.len() - 1]);
+        let (y0, y1) = (self.y[0], self.y[self.y.len() - 1]);
+        let (x, y) = match self.extrapolate {
+            Extrapolate::Clamp => (x.max(x0).min(x1), y.max(y0).min(y1)),
+            Extrapolate::Fill(v) if x < x0 || x > x1 || y < y0 || y > y1 => return v,
+            _ => (x, y),
+        };
+
+        let i = interval(&self.x, &x);
+        let j = interval(&self.y, &y);
+        let (dx, dy) = (x - self.x[i], y - self.y[j]);
+        let c = &self.cells[i * (self.y.len() - 1) + j];
+        c.iter().rev().fold(F::zero(), |acc, cp| {
+            let v = cp.iter().rev().fold(F::zero(), |v, &cq| v * dy + cq);
+            acc * dx + v
+        })
+    }
+
+    /// Returns the interpolated values at the points `(x[k], y[k])`.
+    ///
+    /// Panics if `x` and `y` differ in length.
+    pub fn eval_points(&self, x: &[F], y: &[F]) -> Arr<F> {
+        assert_eq!(x.len(), y.len(), "x and y must have the same length");
+        Arr {
+            values: x.iter().zip(y).map(|(&x, &y)| self.eval(x, y)).collect(),
+        }
+    }
+
+    /// Returns the interpolated values on the grid with axes `x` and `y`,
+    /// as an `x.len()` by `y.len()` matrix.
+    pub fn eval_grid(&self, x: &[F], y: &[F]) -> Mat<F> {
+        let values = x
+            .iter()
+            .flat_map(|&x| y.iter().map(move |&y| self.eval(x, y)))
+            .collect();
+        Mat::new(x.len(), y.len(), values, false)
+    }
+}
+
+/// Returns the values on the grid with axes `x` and `y` interpolated at
+/// the points `(xi[k], yi[k])`, clamping points outside the grid to its
+/// edges. See [`RegularGridInterpolator`].
+pub fn interp2d<F: Float>(
+    x: &[F],
+    y: &[F],
+    values: &Mat<F>,
+    xi: &[F],
+    yi: &[F],
+    method: GridMethod,
+) -> Arr<F> {
+    RegularGridInterpolator::new(x, y, values, method, Extrapolate::Clamp).eval_points(xi, yi)
+}
+
+fn check_axis<F: Float>(a: &[F], name: &str) {
+    assert!(a.len() >= 2, "{} must have at least two values", name);
+    assert!(
+        a.windows(2).all(|w| w[1] > w[0]),
+        "{} must be strictly increasing",
+        name
+    );
+}
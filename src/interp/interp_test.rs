@@ -0,0 +1,181 @@
+use crate::arr::Arr;
+use crate::assert_arr_close;
+use crate::interp::{
+    interp, interp2d, interp_with, CubicSpline, Extrapolate, GridMethod, InterpMethod, Pchip,
+    RegularGridInterpolator, SplineBoundary,
+};
+use crate::mat::Mat;
+
+#[test]
+fn test_interp() {
+    let (xp, fp) = ([1.0, 2.0, 3.0], [3.0, 2.0, 0.0]);
+    assert_arr_close!(
+        interp(&[0.0, 1.0, 1.5, 2.72, 3.2], &xp, &fp),
+        Arr::with_vec(vec![3.0, 3.0, 2.5, 0.56, 0.0])
+    );
+    assert_eq!(Arr::with_vec(vec![2.5]).interp(&xp, &fp).vec(), vec![1.0]);
+    assert!(interp(&[f64::NAN], &xp, &fp)[0].is_nan());
+    assert_eq!(interp(&[-1.0, 5.0], &[2.0], &[7.0]).vec(), vec![7.0, 7.0]);
+
+    let x = [0.0, 1.0, 3.0, 4.0];
+    let y = interp_with(&x, &xp, &fp, InterpMethod::Linear, Extrapolate::Linear);
+    assert_eq!(y.vec(), vec![4.0, 3.0, 0.0, -2.0]);
+    let y = interp_with(
+        &x,
+        &xp,
+        &fp,
+        InterpMethod::Linear,
+        Extrapolate::Fill(f64::NAN),
+    );
+    assert!(y[0].is_nan() && y[3].is_nan());
+    assert_eq!(&y[1..3], &[3.0, 0.0]);
+}
+
+#[test]
+fn test_interp_methods() {
+    let (xp, fp) = ([0.0, 1.0, 2.0], [10.0, 20.0, 30.0]);
+    let x = [-1.0, 0.0, 0.25, 0.5, 0.75, 1.0, 2.0, 3.0];
+    let eval = |method| interp_with(&x, &xp, &fp, method, Extrapolate::Clamp).vec();
+    assert_eq!(
+        eval(InterpMethod::Nearest),
+        vec![10.0, 10.0, 10.0, 10.0, 20.0, 20.0, 30.0, 30.0]
+    );
+    assert_eq!(
+        eval(InterpMethod::Previous),
+        vec![10.0, 10.0, 10.0, 10.0, 10.0, 20.0, 30.0, 30.0]
+    );
+    assert_eq!(
+        eval(InterpMethod::Next),
+        vec![10.0, 10.0, 20.0, 20.0, 20.0, 20.0, 30.0, 30.0]
+    );
+}
+
+#[test]
+fn test_cubic_spline() {
+    let cubic = |x: f64| 1.0 - 2.0 * x + 0.5 * x * x * x;
+    let x = [0.0, 0.5, 1.5, 2.0, 3.5];
+    let y = x.map(cubic);
+    let xi = Arr::linspace(-0.5, 4.0, 19, true);
+    let expected = Arr::with_vec(xi.iter().map(|&x| cubic(x)).collect());
+
+    // Not-a-knot and correctly clamped splines reproduce a cubic.
+    let s = CubicSpline::new(&x, &y, SplineBoundary::NotAKnot);
+    assert_arr_close!(s.eval_arr(&xi), expected, 1e-12, 1e-12);
+    let s = CubicSpline::new(&x, &y, SplineBoundary::Clamped(-2.0, 16.375));
+    assert_arr_close!(s.eval_arr(&xi), expected, 1e-12, 1e-12);
+    assert!((s.derivative(1.0, 1) - (1.5 - 2.0)).abs() < 1e-12);
+    assert!((s.derivative(1.0, 2) - 3.0).abs() < 1e-12);
+    assert!((s.derivative(1.0, 3) - 3.0).abs() < 1e-12);
+
+    // A natural spline has zero curvature at the ends and continuous
+    // curvature at the knots.
+    let s = CubicSpline::new(&x, &y, SplineBoundary::Natural);
+    assert!(s.derivative(0.0, 2).abs() < 1e-12);
+    assert!(s.derivative(3.5, 2).abs() < 1e-12);
+    for &k in &x[1..4] {
+        let below = s.derivative(k - 1e-9, 2);
+        assert!((below - s.derivative(k, 2)).abs() < 1e-6);
+    }
+    assert_arr_close!(s.eval_arr(&x), Arr::with_vec(y.to_vec()), 1e-12, 1e-12);
+
+    // Three points give the interpolating parabola and two a line.
+    let s = CubicSpline::<f64>::new(
+        &[0.0, 1.0, 3.0],
+        &[1.0, 2.0, 10.0],
+        SplineBoundary::NotAKnot,
+    );
+    assert!((s.eval(2.0) - 5.0).abs() < 1e-12);
+    let s = CubicSpline::<f64>::new(&[0.0, 2.0], &[1.0, 2.0], SplineBoundary::default());
+    assert!((s.eval(3.0) - 2.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_pchip() {
+    let p = Pchip::<f64>::new(&[0.0, 1.0, 2.0, 3.0], &[0.0, 1.0, 1.0, 2.0]);
+    assert!((p.eval(1.5) - 1.0).abs() < 1e-12);
+    assert!((p.derivative(0.0, 1) - 1.5).abs() < 1e-12);
+    assert!(p.derivative(1.0, 1).abs() < 1e-12);
+
+    // No overshoot on step-like monotone data.
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [0.0, 0.0, 0.1, 5.0, 5.0, 5.1];
+    let p = Pchip::new(&x, &y);
+    let v = p.eval_arr(&Arr::linspace(0.0, 5.0, 101, true));
+    assert!(v.windows(2).all(|w| w[1] >= w[0] - 1e-12));
+    assert!(v.iter().all(|&v| (0.0..=5.1).contains(&v)));
+    assert_arr_close!(p.eval_arr(&x), Arr::with_vec(y.to_vec()), 1e-12, 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn test_spline_not_increasing() {
+    CubicSpline::new(&[0.0, 2.0, 1.0], &[1.0, 2.0, 3.0], SplineBoundary::Natural);
+}
+
+// Returns the values of `f` on the grid with axes `x` and `y`.
+fn grid_values(x: &[f64], y: &[f64], f: impl Fn(f64, f64) -> f64, col_major: bool) -> Mat<f64> {
+    let mut m = Mat::zeros(x.len(), y.len(), col_major);
+    for (i, &x) in x.iter().enumerate() {
+        for (j, &y) in y.iter().enumerate() {
+            m[(i, j)] = f(x, y);
+        }
+    }
+    m
+}
+
+#[test]
+fn test_grid_bilinear() {
+    let f = |x: f64, y: f64| 1.0 + 2.0 * x - y + 0.5 * x * y;
+    let (x, y) = ([0.0, 1.0, 3.0], [-1.0, 0.0, 0.5, 2.0]);
+    let values = grid_values(&x, &y, f, true);
+    let (xi, yi) = ([0.5, 2.9, 0.0, 1.7], [1.2, -0.3, 2.0, 0.25]);
+    let expected = Arr::with_vec(xi.iter().zip(&yi).map(|(&x, &y)| f(x, y)).collect());
+    let v = interp2d(&x, &y, &values, &xi, &yi, GridMethod::Bilinear);
+    assert_arr_close!(v, expected, 1e-12, 1e-12);
+
+    let g = RegularGridInterpolator::new(&x, &y, &values, GridMethod::Bilinear, Extrapolate::Clamp);
+    assert_eq!(g.eval(-1.0, 5.0), f(0.0, 2.0));
+    let g =
+        RegularGridInterpolator::new(&x, &y, &values, GridMethod::Bilinear, Extrapolate::Linear);
+    assert!((g.eval(4.0, -2.0) - f(4.0, -2.0)).abs() < 1e-12);
+    let g = RegularGridInterpolator::new(
+        &x,
+        &y,
+        &values,
+        GridMethod::Bilinear,
+        Extrapolate::Fill(f64::NAN),
+    );
+    assert!(g.eval(1.0, 2.5).is_nan());
+    assert!(g.eval(f64::NAN, 0.0).is_nan());
+    assert_eq!(g.eval(3.0, 2.0), f(3.0, 2.0));
+
+    let m = g.eval_grid(&[0.0, 1.0], &[0.0, 0.5, 2.0]);
+    assert_eq!(m.shape(), (2, 3));
+    assert!((m[(1, 2)] - f(1.0, 2.0)).abs() < 1e-12);
+}
+
+#[test]
+fn test_grid_bicubic() {
+    let f = |x: f64, y: f64| 1.0 - x * x * x + 2.0 * x * y * y - 0.5 * y * y * y * x * x;
+    let x = [0.0, 0.5, 1.5, 2.0, 3.0];
+    let y = [-1.0, 0.0, 1.0, 1.5, 2.5, 3.0];
+    let values = grid_values(&x, &y, f, false);
+    let g = RegularGridInterpolator::new(&x, &y, &values, GridMethod::Bicubic, Extrapolate::Linear);
+    let xi = [0.2, 1.1, 2.9, 3.5, -0.5];
+    let yi = [2.7, -0.8, 0.3, 3.2, 1.0];
+    let expected = Arr::with_vec(xi.iter().zip(&yi).map(|(&x, &y)| f(x, y)).collect());
+    assert_arr_close!(g.eval_points(&xi, &yi), expected, 1e-10, 1e-10);
+}
+
+#[test]
+#[should_panic]
+fn test_grid_shape() {
+    let values = Mat::zeros(2, 3, false);
+    RegularGridInterpolator::new(
+        &[0.0, 1.0, 2.0],
+        &[0.0, 1.0],
+        &values,
+        GridMethod::Bilinear,
+        Extrapolate::Clamp,
+    );
+}
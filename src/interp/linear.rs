@@ -0,0 +1,110 @@
+use crate::arr::Arr;
+use crate::interp::interval;
+use crate::traits::Float;
+
+/// One dimensional interpolation method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterpMethod {
+    /// Straight lines between the sample points.
+    #[default]
+    Linear,
+    /// The value of the nearest sample point, the lower if equidistant.
+    Nearest,
+    /// The value of the sample point at or before `x`.
+    Previous,
+    /// The value of the sample point at or after `x`.
+    Next,
+}
+
+/// Values returned for points outside the range of the sample points.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Extrapolate<F> {
+    /// The value at the nearest end point, as in NumPy.
+    #[default]
+    Clamp,
+    /// Extends the first and last segments as straight lines.
+    Linear,
+    /// A constant, such as NaN.
+    Fill(F),
+}
+
+/// Returns the piecewise linear interpolation of the sample points
+/// `(xp, fp)` at `x`, with `xp` increasing. Points outside the range of
+/// `xp` take the value at the nearest end point, as in NumPy's `interp`.
+///
+/// Panics if `xp` is empty or differs in length from `fp`.
+pub fn interp<F: Float>(x: &[F], xp: &[F], fp: &[F]) -> Arr<F> {
+    interp_with(x, xp, fp, InterpMethod::Linear, Extrapolate::Clamp)
+}
+
+/// Returns the interpolation of the sample points `(xp, fp)` at `x`
+/// using `method`, with `xp` increasing. Points outside the range of
+/// `xp` are handled according to `extrapolate`.
+///
+/// Panics if `xp` is empty or differs in length from `fp`.
+pub fn interp_with<F: Float>(
+    x: &[F],
+    xp: &[F],
+    fp: &[F],
+    method: InterpMethod,
+    extrapolate: Extrapolate<F>,
+) -> Arr<F> {
+    assert_eq!(xp.len(), fp.len(), "xp and fp must have the same length");
+    assert!(!xp.is_empty(), "xp must not be empty");
+    let n = xp.len();
+
+    let eval = |x: F| {
+        if x.is_nan() {
+            return x;
+        }
+        if n == 1 {
+            return match extrapolate {
+                Extrapolate::Fill(v) if x != xp[0] => v,
+                _ => fp[0],
+            };
+        }
+        let outside = x < xp[0] || x > xp[n - 1];
+        match extrapolate {
+            Extrapolate::Clamp if outside => {
+                return if x < xp[0] { fp[0] } else { fp[n - 1] };
+            }
+            Extrapolate::Fill(v) if outside => return v,
+            _ => {}
+        }
+
+        let i = interval(xp, &x);
+        let (x0, x1) = (xp[i], xp[i + 1]);
+        if outside || method == InterpMethod::Linear {
+            if x == x1 {
+                return fp[i + 1];
+            }
+            let t = (x - x0) / (x1 - x0);
+            return fp[i] + t * (fp[i + 1] - fp[i]);
+        }
+        let upper = match method {
+            InterpMethod::Nearest => x - x0 > x1 - x,
+            InterpMethod::Previous => x >= x1,
+            _ => x > x0,
+        };
+        if upper {
+            fp[i + 1]
+        } else {
+            fp[i]
+        }
+    };
+
+    Arr {
+        values: x.iter().map(|&x| eval(x)).collect(),
+    }
+}
+
+impl<F> Arr<F>
+where
+    F: Float,
+{
+    /// Returns the piecewise linear interpolation of `(xp, fp)` at the
+    /// values of this array. See [`interp`].
+    pub fn interp(&self, xp: &[F], fp: &[F]) -> Arr<F> {
+        interp(self, xp, fp)
+    }
+}
@@ -0,0 +1,17 @@
+mod grid;
+mod linear;
+mod spline;
+
+#[cfg(test)]
+mod interp_test;
+
+pub use grid::*;
+pub use linear::*;
+pub use spline::*;
+
+// Returns the index `i` of the interval `[xp[i], xp[i + 1]]` containing
+// `x`, clamped to the first and last intervals. `xp` must be increasing
+// and have at least two values.
+pub(crate) fn interval<F: PartialOrd>(xp: &[F], x: &F) -> usize {
+    xp.partition_point(|v| v <= x).clamp(1, xp.len() - 1) - 1
+}
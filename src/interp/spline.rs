@@ -0,0 +1,273 @@
+use crate::arr::Arr;
+use crate::interp::interval;
+use crate::traits::Float;
+
+/// End conditions of a cubic spline.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SplineBoundary<F> {
+    /// The third derivative is continuous at the second and second to
+    /// last points, so the first and last two pieces are one cubic.
+    #[default]
+    NotAKnot,
+    /// The second derivative is zero at both ends.
+    Natural,
+    /// The first derivative at the ends is given, as `(start, end)`.
+    Clamped(F, F),
+}
+
+/// Interpolating cubic spline with continuous second derivative.
+///
+/// Points outside the range of the sample points are extrapolated
+/// using the first and last pieces, as in SciPy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicSpline<F> {
+    pub(super) pp: Hermite<F>,
+}
+
+impl<F> CubicSpline<F>
+where
+    F: Float,
+{
+    /// Creates the spline through the points `(x, y)` with the given end
+    /// conditions.
+    ///
+    /// Panics if `x` and `y` differ in length, there are fewer than two
+    /// points or `x` is not strictly increasing.
+    pub fn new(x: &[F], y: &[F], boundary: SplineBoundary<F>) -> Self {
+        let (h, d) = check_points(x, y);
+        let n = x.len();
+        let two = F::one() + F::one();
+        let three = two + F::one();
+
+        let s = match boundary {
+            SplineBoundary::NotAKnot | SplineBoundary::Natural if n == 2 => vec![d[0], d[0]],
+            SplineBoundary::NotAKnot if n == 3 => {
+                // A single parabola through the three points.
+                let c = (d[1] - d[0]) / (h[0] + h[1]);
+                vec![d[0] - c * h[0], d[0] + c * h[0], d[1] + c * h[1]]
+            }
+            _ => {
+                let mut lower = vec![F::zero(); n];
+                let mut diag = vec![F::zero(); n];
+                let mut upper = vec![F::zero(); n];
+                let mut b = vec![F::zero(); n];
+                for i in 1..n - 1 {
+                    lower[i] = h[i];
+                    diag[i] = two * (h[i - 1] + h[i]);
+                    upper[i] = h[i - 1];
+                    b[i] = three * (h[i] * d[i - 1] + h[i - 1] * d[i]);
+                }
+                match boundary {
+                    SplineBoundary::NotAKnot => {
+                        let w = h[0] + h[1];
+                        diag[0] = h[1];
+                        upper[0] = w;
+                        b[0] = ((h[0] + two * w) * h[1] * d[0] + h[0] * h[0] * d[1]) / w;
+                        let w = h[n - 2] + h[n - 3];
+                        lower[n - 1] = w;
+                        diag[n - 1] = h[n - 3];
+                        b[n - 1] = (h[n - 2] * h[n - 2] * d[n - 3]
+                            + (two * w + h[n - 2]) * h[n - 3] * d[n - 2])
+                            / w;
+                    }
+                    SplineBoundary::Natural => {
+                        diag[0] = two;
+                        upper[0] = F::one();
+                        b[0] = three * d[0];
+                        lower[n - 1] = F::one();
+                        diag[n - 1] = two;
+                        b[n - 1] = three * d[n - 2];
+                    }
+                    SplineBoundary::Clamped(start, end) => {
+                        diag[0] = F::one();
+                        b[0] = start;
+                        diag[n - 1] = F::one();
+                        b[n - 1] = end;
+                    }
+                }
+                solve_tridiagonal(&lower, &diag, &upper, b)
+            }
+        };
+
+        Self {
+            pp: Hermite::new(x, y, &s),
+        }
+    }
+
+    /// Returns the value of the spline at `x`.
+    pub fn eval(&self, x: F) -> F {
+        self.pp.eval(x, 0)
+    }
+
+    /// Returns the values of the spline at each of the values in `x`.
+    pub fn eval_arr(&self, x: &[F]) -> Arr<F> {
+        self.pp.eval_arr(x, 0)
+    }
+
+    /// Returns derivative `nu` of the spline at `x`.
+    pub fn derivative(&self, x: F, nu: usize) -> F {
+        self.pp.eval(x, nu)
+    }
+}
+
+/// Piecewise cubic Hermite interpolating polynomial (PCHIP) that
+/// preserves the monotonicity of the data, using the Fritsch-Butland
+/// slopes as in SciPy. It does not overshoot, but only the first
+/// derivative is continuous.
+///
+/// Points outside the range of the sample points are extrapolated
+/// using the first and last pieces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pchip<F> {
+    pp: Hermite<F>,
+}
+
+impl<F> Pchip<F>
+where
+    F: Float,
+{
+    /// Creates the interpolant through the points `(x, y)`.
+    ///
+    /// Panics if `x` and `y` differ in length, there are fewer than two
+    /// points or `x` is not strictly increasing.
+    pub fn new(x: &[F], y: &[F]) -> Self {
+        let (h, d) = check_points(x, y);
+        let n = x.len();
+        let two = F::one() + F::one();
+        let three = two + F::one();
+
+        let mut s = vec![d[0]; n];
+        if n > 2 {
+            for k in 1..n - 1 {
+                s[k] = if d[k - 1].signum() != d[k].signum() || d[k - 1].is_zero() || d[k].is_zero()
+                {
+                    F::zero()
+                } else {
+                    // Weighted harmonic mean of the adjacent slopes.
+                    let w1 = two * h[k] + h[k - 1];
+                    let w2 = h[k] + two * h[k - 1];
+                    (w1 + w2) / (w1 / d[k - 1] + w2 / d[k])
+                };
+            }
+            let edge = |h0: F, h1: F, d0: F, d1: F| {
+                let s = ((two * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+                if s.signum() != d0.signum() || d0.is_zero() {
+                    F::zero()
+                } else if d0.signum() != d1.signum() && s.abs() > three * d0.abs() {
+                    three * d0
+                } else {
+                    s
+                }
+            };
+            s[0] = edge(h[0], h[1], d[0], d[1]);
+            s[n - 1] = edge(h[n - 2], h[n - 3], d[n - 2], d[n - 3]);
+        }
+
+        Self {
+            pp: Hermite::new(x, y, &s),
+        }
+    }
+
+    /// Returns the value of the interpolant at `x`.
+    pub fn eval(&self, x: F) -> F {
+        self.pp.eval(x, 0)
+    }
+
+    /// Returns the values of the interpolant at each of the values in `x`.
+    pub fn eval_arr(&self, x: &[F]) -> Arr<F> {
+        self.pp.eval_arr(x, 0)
+    }
+
+    /// Returns derivative `nu` of the interpolant at `x`.
+    pub fn derivative(&self, x: F, nu: usize) -> F {
+        self.pp.eval(x, nu)
+    }
+}
+
+// Checks the sample points and returns the interval widths and slopes.
+fn check_points<F: Float>(x: &[F], y: &[F]) -> (Vec<F>, Vec<F>) {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    assert!(x.len() >= 2, "at least two points are required");
+    let h: Vec<F> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    assert!(
+        h.iter().all(|&h| h > F::zero()),
+        "x must be strictly increasing"
+    );
+    let d = y
+        .windows(2)
+        .zip(&h)
+        .map(|(w, &h)| (w[1] - w[0]) / h)
+        .collect();
+    (h, d)
+}
+
+// Solves a tridiagonal system with the Thomas algorithm, where `lower[i]`
+// and `upper[i]` are the coefficients left and right of `diag[i]`.
+fn solve_tridiagonal<F: Float>(lower: &[F], diag: &[F], upper: &[F], mut b: Vec<F>) -> Vec<F> {
+    let n = diag.len();
+    let mut c = vec![F::zero(); n];
+    let mut m = diag[0];
+    c[0] = upper[0] / m;
+    b[0] = b[0] / m;
+    for i in 1..n {
+        m = diag[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / m;
+        b[i] = (b[i] - lower[i] * b[i - 1]) / m;
+    }
+    for i in (0..n - 1).rev() {
+        b[i] = b[i] - c[i] * b[i + 1];
+    }
+    b
+}
+
+// Piecewise cubic with the coefficients of each piece in powers of
+// `x - x[i]`, defined by the values and first derivatives at the points.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Hermite<F> {
+    x: Vec<F>,
+    pub(super) c: Vec<[F; 4]>,
+}
+
+impl<F: Float> Hermite<F> {
+    fn new(x: &[F], y: &[F], s: &[F]) -> Self {
+        let two = F::one() + F::one();
+        let three = two + F::one();
+        let c = (0..x.len() - 1)
+            .map(|i| {
+                let h = x[i + 1] - x[i];
+                let d = (y[i + 1] - y[i]) / h;
+                [
+                    y[i],
+                    s[i],
+                    (three * d - two * s[i] - s[i + 1]) / h,
+                    (s[i] + s[i + 1] - two * d) / (h * h),
+                ]
+            })
+            .collect();
+        Self { x: x.to_vec(), c }
+    }
+
+    fn eval(&self, x: F, nu: usize) -> F {
+        if x.is_nan() {
+            return x;
+        }
+        let i = interval(&self.x, &x);
+        let t = x - self.x[i];
+        let c = &self.c[i];
+        let two = F::one() + F::one();
+        let three = two + F::one();
+        match nu {
+            0 => ((c[3] * t + c[2]) * t + c[1]) * t + c[0],
+            1 => (three * c[3] * t + two * c[2]) * t + c[1],
+            2 => two * three * c[3] * t + two * c[2],
+            3 => two * three * c[3],
+            _ => F::zero(),
+        }
+    }
+
+    fn eval_arr(&self, x: &[F], nu: usize) -> Arr<F> {
+        Arr {
+            values: x.iter().map(|&x| self.eval(x, nu)).collect(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::arr::Arr;
+use crate::io::invalid_data;
+use crate::mat::Mat;
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Options for reading delimited text.
+#[derive(Debug, Clone)]
+pub struct CsvOptions<T> {
+    /// Field separator. `None` splits on runs of whitespace, which reads
+    /// the output of `Display for Mat` for matrices of up to 1000 elements.
+    /// Larger matrices are displayed with `...` elisions, so write them
+    /// with `Mat::to_csv` to read them back.
+    pub delimiter: Option<char>,
+    /// Number of leading lines (e.g. headers) to skip.
+    pub skip_rows: usize,
+    /// Value used for empty fields and fields matching `na_values`,
+    /// typically `f64::NAN`. Missing values are an error if `None`.
+    pub missing: Option<T>,
+    /// Field values, in addition to empty fields, that denote missing values.
+    pub na_values: Vec<String>,
+}
+
+impl<T> Default for CsvOptions<T> {
+    fn default() -> Self {
+        Self {
+            delimiter: Some(','),
+            skip_rows: 0,
+            missing: None,
+            na_values: Vec::new(),
+        }
+    }
+}
+
+impl<T> Arr<T>
+where
+    T: FromStr + Copy,
+{
+    /// Reads all fields of delimited text, in row order, into an array.
+    pub fn from_csv<R: BufRead>(r: R, options: &CsvOptions<T>) -> io::Result<Self> {
+        let rows = read_csv(r, options)?;
+        Ok(Self {
+            values: rows.concat(),
+        })
+    }
+
+    /// Writes the array as a single column.
+    pub fn to_csv<W: Write>(&self, mut w: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        for v in &self.values {
+            writeln!(w, "{}", v)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: FromStr + Copy,
+{
+    /// Reads delimited text into a row-major matrix.
+    /// All rows must have the same number of fields.
+    pub fn from_csv<R: BufRead>(r: R, options: &CsvOptions<T>) -> io::Result<Self> {
+        let rows = read_csv(r, options)?;
+        let cols = rows.first().map(|row| row.len()).unwrap_or(0);
+        if let Some(i) = rows.iter().position(|row| row.len() != cols) {
+            return Err(invalid_data(format!(
+                "row {} has {} fields, expected {}",
+                i + 1,
+                rows[i].len(),
+                cols
+            )));
+        }
+        Ok(Mat::new(rows.len(), cols, rows.concat(), false))
+    }
+
+    /// Writes the matrix one row per line with fields separated by `delimiter`.
+    pub fn to_csv<W: Write>(&self, mut w: W, delimiter: char) -> io::Result<()>
+    where
+        T: Display,
+    {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                if j != 0 {
+                    write!(w, "{}", delimiter)?;
+                }
+                write!(w, "{}", self[(i, j)])?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the non-empty lines of delimited text as rows of values.
+fn read_csv<T, R>(r: R, options: &CsvOptions<T>) -> io::Result<Vec<Vec<T>>>
+where
+    T: FromStr + Copy,
+    R: BufRead,
+{
+    let mut rows = Vec::new();
+    for (i, line) in r.lines().enumerate().skip(options.skip_rows) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let row = split_fields(&line, options.delimiter)
+            .iter()
+            .enumerate()
+            .map(|(j, field)| {
+                parse_field(field, options).ok_or_else(|| {
+                    invalid_data(format!(
+                        "invalid value {:?} at line {}, field {}",
+                        field,
+                        i + 1,
+                        j + 1
+                    ))
+                })
+            })
+            .collect::<io::Result<Vec<T>>>()?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_field<T>(field: &str, options: &CsvOptions<T>) -> Option<T>
+where
+    T: FromStr + Copy,
+{
+    if field.is_empty() || options.na_values.iter().any(|na| na == field) {
+        return options.missing;
+    }
+    // Complex values may be parenthesised, e.g. `(1+2j)`.
+    let field = field
+        .strip_prefix('(')
+        .and_then(|f| f.strip_suffix(')'))
+        .unwrap_or(field)
+        .trim();
+    field.parse().ok()
+}
+
+/// Splits a line into trimmed fields. Fields may be enclosed in
+/// double quotes, with `""` denoting a literal quote.
+fn split_fields(line: &str, delimiter: Option<char>) -> Vec<String> {
+    let delimiter = match delimiter {
+        Some(d) => d,
+        None => return line.split_whitespace().map(String::from).collect(),
+    };
+
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
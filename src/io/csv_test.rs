@@ -0,0 +1,72 @@
+use crate::arr::Arr;
+use crate::io::CsvOptions;
+use crate::mat::Mat;
+use num_complex::Complex64;
+
+#[test]
+fn test_csv_mat() {
+    let text = "a,b,c\n1,2,3\n4.5, \"-6\" ,7e1\n\n";
+    let options = CsvOptions {
+        skip_rows: 1,
+        ..Default::default()
+    };
+    let m = Mat::<f64>::from_csv(text.as_bytes(), &options).unwrap();
+    assert_eq!(m.shape(), (2, 3));
+    assert_eq!(m.values(), &[1.0, 2.0, 3.0, 4.5, -6.0, 70.0]);
+
+    let mut buf = Vec::new();
+    m.to_csv(&mut buf, '\t').unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "1\t2\t3\n4.5\t-6\t70\n");
+}
+
+#[test]
+fn test_csv_errors() {
+    let options = CsvOptions::<f64>::default();
+    assert!(Mat::<f64>::from_csv("1,2\n3\n".as_bytes(), &options).is_err());
+    assert!(Mat::<f64>::from_csv("1,x\n".as_bytes(), &options).is_err());
+    assert!(Mat::<f64>::from_csv("1,,2\n".as_bytes(), &options).is_err());
+}
+
+#[test]
+fn test_csv_missing() {
+    let options = CsvOptions {
+        missing: Some(f64::NAN),
+        na_values: vec!["NA".to_string()],
+        ..Default::default()
+    };
+    let a = Arr::<f64>::from_csv("1,,NA\n4\n".as_bytes(), &options).unwrap();
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.is_nan().indices(), vec![1, 2]);
+    assert_eq!(a[3], 4.0);
+}
+
+#[test]
+fn test_csv_display_round_trip() {
+    let mut m = Mat::<Complex64>::zeros(2, 2, true);
+    m[(0, 0)] = Complex64::new(1.0, 2.0);
+    m[(0, 1)] = Complex64::new(-0.5, -3.0);
+    m[(1, 1)] = Complex64::new(0.0, 1.0);
+
+    let options = CsvOptions {
+        delimiter: None,
+        ..Default::default()
+    };
+    let n = Mat::<Complex64>::from_csv(m.to_string().as_bytes(), &options).unwrap();
+    assert_eq!(n.shape(), m.shape());
+    for i in 0..2 {
+        for j in 0..2 {
+            assert_eq!(n[(i, j)], m[(i, j)]);
+        }
+    }
+
+    let a =
+        Arr::<Complex64>::from_csv("(1+2j),3-4i,5i".as_bytes(), &CsvOptions::default()).unwrap();
+    assert_eq!(
+        a.values(),
+        &[
+            Complex64::new(1.0, 2.0),
+            Complex64::new(3.0, -4.0),
+            Complex64::new(0.0, 5.0)
+        ]
+    );
+}
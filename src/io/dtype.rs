@@ -0,0 +1,167 @@
+use num_complex::Complex;
+
+/// Element types that can be stored in binary array files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DType {
+    I8 = 1,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    /// Complex value with `f32` parts.
+    C64,
+    /// Complex value with `f64` parts.
+    C128,
+}
+
+const DTYPES: [DType; 12] = [
+    DType::I8,
+    DType::I16,
+    DType::I32,
+    DType::I64,
+    DType::U8,
+    DType::U16,
+    DType::U32,
+    DType::U64,
+    DType::F32,
+    DType::F64,
+    DType::C64,
+    DType::C128,
+];
+
+impl DType {
+    /// Returns the size of one element in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            DType::I8 | DType::U8 => 1,
+            DType::I16 | DType::U16 => 2,
+            DType::I32 | DType::U32 | DType::F32 => 4,
+            DType::I64 | DType::U64 | DType::F64 | DType::C64 => 8,
+            DType::C128 => 16,
+        }
+    }
+
+    /// Returns a unique code identifying the type in binary headers.
+    pub(crate) fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    pub(crate) fn from_code(code: u8) -> Option<DType> {
+        DTYPES.iter().copied().find(|d| d.code() == code)
+    }
+
+    /// Returns true for complex element types.
+    pub fn is_complex(&self) -> bool {
+        matches!(self, DType::C64 | DType::C128)
+    }
+
+    /// Returns the size in bytes of the scalar parts of an element.
+    /// This is the unit to which byte order applies.
+    pub(crate) fn part_size(&self) -> usize {
+        if self.is_complex() {
+            self.size() / 2
+        } else {
+            self.size()
+        }
+    }
+}
+
+/// Reverses the byte order of each scalar part of the elements in `buf`.
+pub(crate) fn swap_bytes(buf: &mut [u8], dtype: DType) {
+    buf.chunks_mut(dtype.part_size())
+        .for_each(|part| part.reverse());
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A value that can be encoded as little-endian bytes.
+///
+/// This trait is sealed: implementors are plain old data whose
+/// in-memory representation on a little-endian host is their encoding.
+pub trait Element: Copy + sealed::Sealed {
+    const DTYPE: DType;
+
+    /// Decodes a value from `DTYPE.size()` little-endian bytes.
+    fn read_le(b: &[u8]) -> Self;
+
+    /// Appends the little-endian encoding of the value to `w`.
+    fn write_le(&self, w: &mut Vec<u8>);
+}
+
+macro_rules! impl_element {
+    ($t:ty, $dtype:expr) => {
+        impl sealed::Sealed for $t {}
+
+        impl Element for $t {
+            const DTYPE: DType = $dtype;
+
+            fn read_le(b: &[u8]) -> Self {
+                <$t>::from_le_bytes(b.try_into().unwrap())
+            }
+
+            fn write_le(&self, w: &mut Vec<u8>) {
+                w.extend_from_slice(&self.to_le_bytes())
+            }
+        }
+    };
+}
+
+impl_element!(i8, DType::I8);
+impl_element!(i16, DType::I16);
+impl_element!(i32, DType::I32);
+impl_element!(i64, DType::I64);
+impl_element!(u8, DType::U8);
+impl_element!(u16, DType::U16);
+impl_element!(u32, DType::U32);
+impl_element!(u64, DType::U64);
+impl_element!(f32, DType::F32);
+impl_element!(f64, DType::F64);
+
+impl sealed::Sealed for Complex<f32> {}
+
+impl Element for Complex<f32> {
+    const DTYPE: DType = DType::C64;
+
+    fn read_le(b: &[u8]) -> Self {
+        Complex::new(f32::read_le(&b[..4]), f32::read_le(&b[4..]))
+    }
+
+    fn write_le(&self, w: &mut Vec<u8>) {
+        self.re.write_le(w);
+        self.im.write_le(w);
+    }
+}
+
+impl sealed::Sealed for Complex<f64> {}
+
+impl Element for Complex<f64> {
+    const DTYPE: DType = DType::C128;
+
+    fn read_le(b: &[u8]) -> Self {
+        Complex::new(f64::read_le(&b[..8]), f64::read_le(&b[8..]))
+    }
+
+    fn write_le(&self, w: &mut Vec<u8>) {
+        self.re.write_le(w);
+        self.im.write_le(w);
+    }
+}
+
+/// Decodes a little-endian buffer into values of type `T`.
+pub(crate) fn decode_le<T: Element>(buf: &[u8]) -> Vec<T> {
+    buf.chunks_exact(T::DTYPE.size()).map(T::read_le).collect()
+}
+
+/// Encodes values of type `T` as little-endian bytes.
+pub(crate) fn encode_le<T: Element>(values: &[T]) -> Vec<u8> {
+    let mut w = Vec::with_capacity(values.len() * T::DTYPE.size());
+    values.iter().for_each(|v| v.write_le(&mut w));
+    w
+}
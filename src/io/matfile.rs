@@ -0,0 +1,495 @@
+use crate::arr::Arr;
+use crate::io::invalid_data;
+use crate::mat::Mat;
+
+use flate2::read::ZlibDecoder;
+use num_complex::Complex;
+use std::io::{self, Read, Write};
+
+const HEADER_LEN: usize = 128;
+
+// Data types.
+const MI_INT8: u32 = 1;
+const MI_UINT8: u32 = 2;
+const MI_INT16: u32 = 3;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_SINGLE: u32 = 7;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_UINT64: u32 = 13;
+const MI_MATRIX: u32 = 14;
+const MI_COMPRESSED: u32 = 15;
+
+// Array classes.
+const MX_CELL_CLASS: u8 = 1;
+const MX_STRUCT_CLASS: u8 = 2;
+const MX_OBJECT_CLASS: u8 = 3;
+const MX_CHAR_CLASS: u8 = 4;
+const MX_SPARSE_CLASS: u8 = 5;
+const MX_DOUBLE_CLASS: u8 = 6;
+const MX_SINGLE_CLASS: u8 = 7;
+const MX_UINT64_CLASS: u8 = 15;
+
+const COMPLEX_FLAG: u32 = 0x0800;
+
+/// Values that can be stored in MAT-files.
+pub trait MatElement: Copy {
+    /// True if values are stored as `single` rather than `double`.
+    const SINGLE: bool;
+    const COMPLEX: bool;
+
+    fn from_parts(re: f64, im: f64) -> Self;
+    fn parts(&self) -> (f64, f64);
+}
+
+impl MatElement for f64 {
+    const SINGLE: bool = false;
+    const COMPLEX: bool = false;
+
+    fn from_parts(re: f64, _im: f64) -> Self {
+        re
+    }
+    fn parts(&self) -> (f64, f64) {
+        (*self, 0.0)
+    }
+}
+
+impl MatElement for f32 {
+    const SINGLE: bool = true;
+    const COMPLEX: bool = false;
+
+    fn from_parts(re: f64, _im: f64) -> Self {
+        re as f32
+    }
+    fn parts(&self) -> (f64, f64) {
+        (*self as f64, 0.0)
+    }
+}
+
+impl MatElement for Complex<f64> {
+    const SINGLE: bool = false;
+    const COMPLEX: bool = true;
+
+    fn from_parts(re: f64, im: f64) -> Self {
+        Complex::new(re, im)
+    }
+    fn parts(&self) -> (f64, f64) {
+        (self.re, self.im)
+    }
+}
+
+impl MatElement for Complex<f32> {
+    const SINGLE: bool = true;
+    const COMPLEX: bool = true;
+
+    fn from_parts(re: f64, im: f64) -> Self {
+        Complex::new(re as f32, im as f32)
+    }
+    fn parts(&self) -> (f64, f64) {
+        (self.re as f64, self.im as f64)
+    }
+}
+
+/// A named variable read from a MAT-file.
+struct Variable {
+    name: String,
+    class: u8,
+    dims: Vec<usize>,
+    re: Vec<f64>,
+    im: Option<Vec<f64>>,
+}
+
+/// Reads named numeric arrays from a MATLAB Level 5 MAT-file.
+///
+/// Variables of any numeric class can be read, including those
+/// in zlib-compressed elements. Cell, struct, object, char and
+/// sparse variables are listed but cannot be read.
+pub struct MatReader {
+    variables: Vec<Variable>,
+}
+
+impl MatReader {
+    /// Reads the complete file from `r`.
+    pub fn new<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        if buf.len() < HEADER_LEN {
+            return Err(invalid_data("not a MAT-file"));
+        }
+
+        let big_endian = match &buf[126..HEADER_LEN] {
+            b"IM" => false,
+            b"MI" => true,
+            _ => return Err(invalid_data("not a MAT-file")),
+        };
+
+        let mut variables = Vec::new();
+        let mut c = Cursor::new(&buf[HEADER_LEN..], big_endian);
+        while !c.is_empty() {
+            let (data_type, data) = c.element()?;
+            match data_type {
+                MI_MATRIX => variables.push(read_matrix(data, big_endian)?),
+                MI_COMPRESSED => {
+                    let mut inflated = Vec::new();
+                    ZlibDecoder::new(data).read_to_end(&mut inflated)?;
+                    let (data_type, data) = Cursor::new(&inflated, big_endian).element()?;
+                    if data_type != MI_MATRIX {
+                        return Err(invalid_data(format!(
+                            "unsupported compressed data type {}",
+                            data_type
+                        )));
+                    }
+                    variables.push(read_matrix(data, big_endian)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self { variables })
+    }
+
+    /// Returns the names of the variables in the file.
+    pub fn names(&self) -> Vec<&str> {
+        self.variables.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    /// Reads the named variable as a column-major matrix.
+    pub fn mat<T: MatElement>(&self, name: &str) -> io::Result<Mat<T>> {
+        let v = self.variable(name)?;
+        if v.dims.len() != 2 {
+            return Err(invalid_data(format!(
+                "variable {:?} has {} dimensions, expected 2",
+                name,
+                v.dims.len()
+            )));
+        }
+        Ok(Mat::new(v.dims[0], v.dims[1], values(v)?, true))
+    }
+
+    /// Reads the named row or column vector.
+    pub fn arr<T: MatElement>(&self, name: &str) -> io::Result<Arr<T>> {
+        let v = self.variable(name)?;
+        if v.dims.iter().filter(|&&d| d != 1).count() > 1 {
+            return Err(invalid_data(format!(
+                "variable {:?} with dimensions {:?} is not a vector",
+                name, v.dims
+            )));
+        }
+        Ok(Arr { values: values(v)? })
+    }
+
+    fn variable(&self, name: &str) -> io::Result<&Variable> {
+        let v = self
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("variable {:?} not found in MAT-file", name),
+                )
+            })?;
+        if !(MX_DOUBLE_CLASS..=MX_UINT64_CLASS).contains(&v.class) {
+            return Err(invalid_data(format!(
+                "variable {:?} has unsupported class {}",
+                name,
+                class_name(v.class)
+            )));
+        }
+        Ok(v)
+    }
+}
+
+fn values<T: MatElement>(v: &Variable) -> io::Result<Vec<T>> {
+    match &v.im {
+        Some(_) if !T::COMPLEX => Err(invalid_data(format!("variable {:?} is complex", v.name))),
+        Some(im) => Ok(v
+            .re
+            .iter()
+            .zip(im)
+            .map(|(&re, &im)| T::from_parts(re, im))
+            .collect()),
+        None => Ok(v.re.iter().map(|&re| T::from_parts(re, 0.0)).collect()),
+    }
+}
+
+fn class_name(class: u8) -> String {
+    match class {
+        MX_CELL_CLASS => "cell".to_string(),
+        MX_STRUCT_CLASS => "struct".to_string(),
+        MX_OBJECT_CLASS => "object".to_string(),
+        MX_CHAR_CLASS => "char".to_string(),
+        MX_SPARSE_CLASS => "sparse".to_string(),
+        c => format!("{}", c),
+    }
+}
+
+/// Reads the sub-elements of an `miMATRIX` data element.
+fn read_matrix(data: &[u8], big_endian: bool) -> io::Result<Variable> {
+    let mut c = Cursor::new(data, big_endian);
+
+    let (_, flags) = c.element()?;
+    let flags = Cursor::new(flags, big_endian).u32()?;
+    let class = (flags & 0xff) as u8;
+
+    let (data_type, dims) = c.element()?;
+    let dims = decode(data_type, dims, big_endian)?
+        .into_iter()
+        .map(|d| d as usize)
+        .collect::<Vec<usize>>();
+
+    let (_, name) = c.element()?;
+    let name = String::from_utf8_lossy(name).into_owned();
+
+    let mut v = Variable {
+        name,
+        class,
+        dims,
+        re: Vec::new(),
+        im: None,
+    };
+    if !(MX_DOUBLE_CLASS..=MX_UINT64_CLASS).contains(&class) {
+        return Ok(v);
+    }
+
+    let n = v
+        .dims
+        .iter()
+        .try_fold(1usize, |n, &d| n.checked_mul(d))
+        .ok_or_else(|| invalid_data(format!("variable {:?} is too large", v.name)))?;
+    let (data_type, re) = c.element()?;
+    v.re = decode(data_type, re, big_endian)?;
+    if flags & COMPLEX_FLAG != 0 {
+        let (data_type, im) = c.element()?;
+        v.im = Some(decode(data_type, im, big_endian)?);
+    }
+    if v.re.len() != n || v.im.as_ref().is_some_and(|im| im.len() != n) {
+        return Err(invalid_data(format!(
+            "variable {:?} has {} values, expected {}",
+            v.name,
+            v.re.len(),
+            n
+        )));
+    }
+    Ok(v)
+}
+
+/// Decodes numeric data of any type as `f64` values.
+fn decode(data_type: u32, data: &[u8], big_endian: bool) -> io::Result<Vec<f64>> {
+    macro_rules! decode_as {
+        ($t:ty) => {
+            data.chunks_exact(std::mem::size_of::<$t>())
+                .map(|b| {
+                    let b = b.try_into().unwrap();
+                    if big_endian {
+                        <$t>::from_be_bytes(b) as f64
+                    } else {
+                        <$t>::from_le_bytes(b) as f64
+                    }
+                })
+                .collect()
+        };
+    }
+    Ok(match data_type {
+        MI_INT8 => decode_as!(i8),
+        MI_UINT8 => decode_as!(u8),
+        MI_INT16 => decode_as!(i16),
+        MI_UINT16 => decode_as!(u16),
+        MI_INT32 => decode_as!(i32),
+        MI_UINT32 => decode_as!(u32),
+        MI_SINGLE => decode_as!(f32),
+        MI_DOUBLE => decode_as!(f64),
+        MI_INT64 => decode_as!(i64),
+        MI_UINT64 => decode_as!(u64),
+        t => return Err(invalid_data(format!("unsupported data type {}", t))),
+    })
+}
+
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8], big_endian: bool) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            big_endian,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    fn bytes(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        let b = self
+            .buf
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| invalid_data("truncated MAT-file"))?;
+        self.pos += n;
+        Ok(b)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let b = self.bytes(4)?.try_into().unwrap();
+        Ok(if self.big_endian {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        })
+    }
+
+    /// Reads a data element returning its type and data.
+    fn element(&mut self) -> io::Result<(u32, &'a [u8])> {
+        let tag = self.u32()?;
+
+        // Elements of at most 4 bytes may be packed with the
+        // type and size sharing the first word of the tag.
+        if tag >> 16 != 0 {
+            let n = (tag >> 16) as usize;
+            let data = self.bytes(4)?;
+            return Ok((tag & 0xffff, &data[..n.min(4)]));
+        }
+
+        let n = self.u32()? as usize;
+        let data = self.bytes(n)?;
+        if tag != MI_COMPRESSED {
+            self.pos += padding(n);
+        }
+        Ok((tag, data))
+    }
+}
+
+fn padding(n: usize) -> usize {
+    (8 - n % 8) % 8
+}
+
+/// Writes named arrays to a MATLAB Level 5 MAT-file.
+pub struct MatWriter<W: Write> {
+    w: W,
+}
+
+impl<W: Write> MatWriter<W> {
+    /// Writes the file header to `w`.
+    pub fn new(mut w: W) -> io::Result<Self> {
+        let mut header = format!(
+            "MATLAB 5.0 MAT-file, Platform: {}, Created by: full",
+            std::env::consts::OS
+        )
+        .into_bytes();
+        header.resize(116, b' ');
+        header.extend_from_slice(&[0; 8]); // subsystem data offset
+        header.extend_from_slice(&0x0100u16.to_le_bytes()); // version
+        header.extend_from_slice(b"IM");
+        w.write_all(&header)?;
+        Ok(Self { w })
+    }
+
+    /// Adds a matrix to the file.
+    pub fn add_mat<T: MatElement>(&mut self, name: &str, m: &Mat<T>) -> io::Result<()> {
+        if m.col_major {
+            self.add(name, m.rows, m.cols, &m.values)
+        } else {
+            let values: Vec<T> = (0..m.cols).flat_map(|j| m.col(j).copied()).collect();
+            self.add(name, m.rows, m.cols, &values)
+        }
+    }
+
+    /// Adds an array to the file as a row vector.
+    pub fn add_arr<T: MatElement>(&mut self, name: &str, a: &Arr<T>) -> io::Result<()> {
+        self.add(name, 1, a.len(), &a.values)
+    }
+
+    fn add<T: MatElement>(
+        &mut self,
+        name: &str,
+        rows: usize,
+        cols: usize,
+        values: &[T],
+    ) -> io::Result<()> {
+        if !valid_name(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid MATLAB variable name {:?}", name),
+            ));
+        }
+
+        let class = if T::SINGLE {
+            MX_SINGLE_CLASS
+        } else {
+            MX_DOUBLE_CLASS
+        };
+        let flags = class as u32 | if T::COMPLEX { COMPLEX_FLAG } else { 0 };
+        let dim = |d: usize| {
+            i32::try_from(d).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("dimension {} exceeds the MAT-file limit", d),
+                )
+            })
+        };
+        let (rows, cols) = (dim(rows)?, dim(cols)?);
+
+        let mut m = Vec::new();
+        write_element(&mut m, MI_UINT32, &[flags.to_le_bytes(), [0; 4]].concat())?;
+        write_element(
+            &mut m,
+            MI_INT32,
+            &[rows.to_le_bytes(), cols.to_le_bytes()].concat(),
+        )?;
+        write_element(&mut m, MI_INT8, name.as_bytes())?;
+
+        let mut parts = vec![values.iter().map(|v| v.parts().0).collect::<Vec<f64>>()];
+        if T::COMPLEX {
+            parts.push(values.iter().map(|v| v.parts().1).collect());
+        }
+        for part in parts {
+            if T::SINGLE {
+                let data: Vec<u8> = part
+                    .iter()
+                    .flat_map(|&v| (v as f32).to_le_bytes())
+                    .collect();
+                write_element(&mut m, MI_SINGLE, &data)?;
+            } else {
+                let data: Vec<u8> = part.iter().flat_map(|v| v.to_le_bytes()).collect();
+                write_element(&mut m, MI_DOUBLE, &data)?;
+            }
+        }
+
+        let mut element = Vec::with_capacity(8 + m.len());
+        write_element(&mut element, MI_MATRIX, &m)?;
+        self.w.write_all(&element)
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.w.flush()?;
+        Ok(self.w)
+    }
+}
+
+fn write_element(w: &mut Vec<u8>, data_type: u32, data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "MAT-file element exceeds 4 GiB",
+        )
+    })?;
+    w.extend_from_slice(&data_type.to_le_bytes());
+    w.extend_from_slice(&len.to_le_bytes());
+    w.extend_from_slice(data);
+    w.resize(w.len() + padding(data.len()), 0);
+    Ok(())
+}
+
+/// Returns true if `name` is a valid MATLAB identifier.
+fn valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    name.len() <= 63
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,115 @@
+use crate::arr::Arr;
+use crate::io::{MatReader, MatWriter};
+use crate::mat::Mat;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use num_complex::{Complex32, Complex64};
+use std::io::Write;
+
+fn element(data_type: u32, data: &[u8]) -> Vec<u8> {
+    let mut e = [data_type.to_le_bytes(), (data.len() as u32).to_le_bytes()].concat();
+    e.extend_from_slice(data);
+    e.resize(e.len() + (8 - data.len() % 8) % 8, 0);
+    e
+}
+
+fn small_element(data_type: u32, data: &[u8]) -> Vec<u8> {
+    let mut e = (data_type | (data.len() as u32) << 16)
+        .to_le_bytes()
+        .to_vec();
+    e.extend_from_slice(data);
+    e.resize(8, 0);
+    e
+}
+
+#[test]
+fn test_matfile() {
+    let mut m = Mat::<f64>::zeros(2, 3, false);
+    for r in 0..2 {
+        for c in 0..3 {
+            m[(r, c)] = (10 * r + c) as f64;
+        }
+    }
+    let z = Mat::<Complex32>::identity(2, true) * Complex32::new(1.0, -2.0);
+    let a = Arr::<f64>::linspace(0.0, 1.0, 3, true);
+
+    let mut w = MatWriter::new(Vec::new()).unwrap();
+    w.add_mat("m", &m).unwrap();
+    w.add_mat("z", &z).unwrap();
+    w.add_arr("a", &a).unwrap();
+    assert!(w.add_arr("1a", &a).is_err());
+    let big = Mat::<f64>::new(1 << 31, 0, vec![], true);
+    let err = w.add_mat("big", &big).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    let buf = w.finish().unwrap();
+    assert!(buf.starts_with(b"MATLAB 5.0 MAT-file"));
+    assert_eq!(buf.len() % 8, 0);
+
+    let r = MatReader::new(buf.as_slice()).unwrap();
+    assert_eq!(r.names(), vec!["m", "z", "a"]);
+
+    let n = r.mat::<f64>("m").unwrap();
+    assert!(n.col_major());
+    assert_eq!(n.shape(), (2, 3));
+    assert_eq!(n.values(), &[0.0, 10.0, 1.0, 11.0, 2.0, 12.0]);
+
+    let y = r.mat::<Complex32>("z").unwrap();
+    assert_eq!(y.values(), z.values());
+    assert!(r.mat::<f32>("z").is_err());
+    let y = r.mat::<Complex64>("z").unwrap();
+    assert_eq!(y[(1, 1)], Complex64::new(1.0, -2.0));
+
+    assert_eq!(r.arr::<f64>("a").unwrap().values(), a.values());
+    assert_eq!(r.mat::<f64>("a").unwrap().shape(), (1, 3));
+    assert!(r.arr::<f64>("m").is_err());
+    assert!(r.arr::<f64>("b").is_err());
+}
+
+#[test]
+fn test_matfile_compressed() {
+    // A double column vector stored as packed miUINT8 data in a
+    // compressed element, as written by MATLAB.
+    let mut x = element(6, &[6u32.to_le_bytes(), [0; 4]].concat());
+    x.extend(element(
+        5,
+        &[2i32.to_le_bytes(), 1i32.to_le_bytes()].concat(),
+    ));
+    x.extend(small_element(1, b"x"));
+    x.extend(small_element(2, &[3, 250]));
+    let mut z = ZlibEncoder::new(Vec::new(), Compression::default());
+    z.write_all(&element(14, &x)).unwrap();
+    let z = z.finish().unwrap();
+
+    let mut s = element(6, &[2u32.to_le_bytes(), [0; 4]].concat());
+    s.extend(element(
+        5,
+        &[1i32.to_le_bytes(), 1i32.to_le_bytes()].concat(),
+    ));
+    s.extend(small_element(1, b"s"));
+
+    let mut buf = MatWriter::new(Vec::new()).unwrap().finish().unwrap();
+    buf.extend_from_slice(&15u32.to_le_bytes());
+    buf.extend_from_slice(&(z.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&z);
+    buf.extend(element(14, &s));
+
+    let r = MatReader::new(buf.as_slice()).unwrap();
+    assert_eq!(r.names(), vec!["x", "s"]);
+    assert_eq!(r.arr::<f64>("x").unwrap().values(), &[3.0, 250.0]);
+    let err = r.mat::<f64>("s").err().unwrap();
+    assert!(err.to_string().contains("unsupported class struct"));
+}
+
+#[test]
+fn test_matfile_too_large() {
+    // Three dimensions of i32::MAX overflow the element count.
+    let mut v = element(6, &[6u32.to_le_bytes(), [0; 4]].concat());
+    v.extend(element(5, &i32::MAX.to_le_bytes().repeat(3)));
+    v.extend(small_element(1, b"v"));
+    v.extend(element(9, &1.0f64.to_le_bytes()));
+
+    let mut buf = MatWriter::new(Vec::new()).unwrap().finish().unwrap();
+    buf.extend(element(14, &v));
+    let err = MatReader::new(buf.as_slice()).err().unwrap();
+    assert!(err.to_string().contains("too large"));
+}
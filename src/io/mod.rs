@@ -0,0 +1,33 @@
+mod csv;
+mod dtype;
+mod matfile;
+mod npy;
+mod npz;
+mod snapshot;
+
+#[cfg(test)]
+mod csv_test;
+#[cfg(test)]
+mod matfile_test;
+#[cfg(test)]
+mod npy_test;
+#[cfg(test)]
+mod snapshot_test;
+
+pub use csv::*;
+pub use dtype::*;
+pub use matfile::*;
+pub use npy::*;
+pub use npz::*;
+pub use snapshot::*;
+
+use std::error::Error;
+use std::io;
+
+/// Returns an `InvalidData` I/O error.
+pub(crate) fn invalid_data<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
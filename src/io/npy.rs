@@ -0,0 +1,282 @@
+use crate::arr::Arr;
+use crate::io::dtype::{decode_le, encode_le, swap_bytes, DType, Element};
+use crate::io::invalid_data;
+use crate::mat::Mat;
+
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+// Header lengths are padded so that the data is aligned to this many bytes.
+const ALIGN: usize = 64;
+
+/// Header of an `.npy` file.
+struct Header {
+    dtype: DType,
+    big_endian: bool,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+impl<T> Arr<T>
+where
+    T: Element,
+{
+    /// Reads a one-dimensional array in NumPy `.npy` format.
+    pub fn read_npy<R: Read>(r: R) -> io::Result<Self> {
+        let (shape, _, values) = read_npy(r)?;
+        if shape.len() != 1 {
+            return Err(invalid_data(format!(
+                "expected 1-dimensional array, found shape {:?}",
+                shape
+            )));
+        }
+        Ok(Self { values })
+    }
+
+    /// Writes the array in NumPy `.npy` format.
+    pub fn write_npy<W: Write>(&self, w: W) -> io::Result<()> {
+        write_npy(w, &[self.values.len()], false, &self.values)
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Element,
+{
+    /// Reads a two-dimensional array in NumPy `.npy` format.
+    /// Arrays with `fortran_order` set are read into a column-major matrix.
+    pub fn read_npy<R: Read>(r: R) -> io::Result<Self> {
+        let (shape, fortran_order, values) = read_npy(r)?;
+        if shape.len() != 2 {
+            return Err(invalid_data(format!(
+                "expected 2-dimensional array, found shape {:?}",
+                shape
+            )));
+        }
+        Ok(Mat::new(shape[0], shape[1], values, fortran_order))
+    }
+
+    /// Writes the matrix in NumPy `.npy` format. Column-major
+    /// matrices are written with `fortran_order` set.
+    pub fn write_npy<W: Write>(&self, w: W) -> io::Result<()> {
+        write_npy(w, &[self.rows, self.cols], self.col_major, &self.values)
+    }
+}
+
+/// Reads an `.npy` file returning the shape, the `fortran_order`
+/// flag and the element values in storage order.
+pub fn read_npy<T, R>(mut r: R) -> io::Result<(Vec<usize>, bool, Vec<T>)>
+where
+    T: Element,
+    R: Read,
+{
+    let header = read_header(&mut r)?;
+    if header.dtype != T::DTYPE {
+        return Err(invalid_data(format!(
+            "dtype mismatch: file has {}, expected {}",
+            descr(header.dtype),
+            descr(T::DTYPE)
+        )));
+    }
+
+    let len = header
+        .shape
+        .iter()
+        .try_fold(header.dtype.size(), |n, &d| n.checked_mul(d))
+        .ok_or_else(|| invalid_data("npy array too large"))?;
+    // Grow the buffer as the data is read rather than trusting the
+    // header with a single allocation.
+    let mut buf = Vec::new();
+    r.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "truncated npy data",
+        ));
+    }
+    if header.big_endian {
+        swap_bytes(&mut buf, header.dtype);
+    }
+
+    Ok((header.shape, header.fortran_order, decode_le(&buf)))
+}
+
+/// Writes `values` in `.npy` format with the given `shape`.
+pub fn write_npy<T, W>(
+    mut w: W,
+    shape: &[usize],
+    fortran_order: bool,
+    values: &[T],
+) -> io::Result<()>
+where
+    T: Element,
+    W: Write,
+{
+    assert_eq!(shape.iter().product::<usize>(), values.len());
+
+    let order = if T::DTYPE.size() == 1 { '|' } else { '<' };
+    let shape = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+    let mut dict = format!(
+        "{{'descr': '{}{}', 'fortran_order': {}, 'shape': {}, }}",
+        order,
+        descr(T::DTYPE),
+        if fortran_order { "True" } else { "False" },
+        shape
+    );
+
+    // Version 1.0 has a 2 byte header length, version 2.0 has 4 bytes.
+    let header_len = |prefix: usize| (prefix + dict.len() + 1).div_ceil(ALIGN) * ALIGN - prefix;
+    let v1 = header_len(MAGIC.len() + 4) <= u16::MAX as usize;
+    let len = header_len(MAGIC.len() + if v1 { 4 } else { 6 });
+    dict.push_str(&" ".repeat(len - dict.len() - 1));
+    dict.push('\n');
+
+    w.write_all(MAGIC)?;
+    if v1 {
+        w.write_all(&[1, 0])?;
+        w.write_all(&(dict.len() as u16).to_le_bytes())?;
+    } else {
+        w.write_all(&[2, 0])?;
+        w.write_all(&(dict.len() as u32).to_le_bytes())?;
+    }
+    w.write_all(dict.as_bytes())?;
+    w.write_all(&encode_le(values))
+}
+
+/// Returns the type code of `dtype` without the byte order character.
+fn descr(dtype: DType) -> &'static str {
+    match dtype {
+        DType::I8 => "i1",
+        DType::I16 => "i2",
+        DType::I32 => "i4",
+        DType::I64 => "i8",
+        DType::U8 => "u1",
+        DType::U16 => "u2",
+        DType::U32 => "u4",
+        DType::U64 => "u8",
+        DType::F32 => "f4",
+        DType::F64 => "f8",
+        DType::C64 => "c8",
+        DType::C128 => "c16",
+    }
+}
+
+/// Parses a `descr` value such as `<f8` into a type and a big-endian flag.
+fn parse_descr(s: &str) -> io::Result<(DType, bool)> {
+    let code = s.get(1..).unwrap_or_default();
+    let (big_endian, code) = match s.chars().next() {
+        Some('<') | Some('|') => (false, code),
+        Some('>') => (true, code),
+        Some('=') => (cfg!(target_endian = "big"), code),
+        _ => (false, s),
+    };
+    let dtype = match code {
+        "i1" => DType::I8,
+        "i2" => DType::I16,
+        "i4" => DType::I32,
+        "i8" => DType::I64,
+        "u1" => DType::U8,
+        "u2" => DType::U16,
+        "u4" => DType::U32,
+        "u8" => DType::U64,
+        "f4" => DType::F32,
+        "f8" => DType::F64,
+        "c8" => DType::C64,
+        "c16" => DType::C128,
+        _ => return Err(invalid_data(format!("unsupported dtype {:?}", s))),
+    };
+    Ok((dtype, big_endian))
+}
+
+fn read_header<R: Read>(r: &mut R) -> io::Result<Header> {
+    let mut magic = [0; 8];
+    r.read_exact(&mut magic)?;
+    if &magic[..6] != MAGIC {
+        return Err(invalid_data("not an npy file"));
+    }
+
+    let header_len = match magic[6] {
+        1 => {
+            let mut b = [0; 2];
+            r.read_exact(&mut b)?;
+            u16::from_le_bytes(b) as usize
+        }
+        2 | 3 => {
+            let mut b = [0; 4];
+            r.read_exact(&mut b)?;
+            u32::from_le_bytes(b) as usize
+        }
+        v => return Err(invalid_data(format!("unsupported npy version {}", v))),
+    };
+
+    let mut dict = vec![0; header_len];
+    r.read_exact(&mut dict)?;
+    let dict = String::from_utf8(dict).map_err(invalid_data)?;
+
+    let descr = dict_value(&dict, "descr")?;
+    let code = descr
+        .strip_prefix(['\'', '"'])
+        .and_then(|d| d.strip_suffix(['\'', '"']))
+        .ok_or_else(|| invalid_data(format!("unsupported dtype {}", descr)))?;
+    let (dtype, big_endian) = parse_descr(code)?;
+
+    let fortran_order = match dict_value(&dict, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        v => return Err(invalid_data(format!("invalid fortran_order {}", v))),
+    };
+
+    let shape = dict_value(&dict, "shape")?;
+    let shape = shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|d| d.trim())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse::<usize>().map_err(invalid_data))
+        .collect::<io::Result<Vec<usize>>>()?;
+
+    Ok(Header {
+        dtype,
+        big_endian,
+        fortran_order,
+        shape,
+    })
+}
+
+/// Returns the source text of the value for `key` in a Python dict literal.
+fn dict_value<'a>(dict: &'a str, key: &str) -> io::Result<&'a str> {
+    let missing = || invalid_data(format!("npy header missing {:?}", key));
+
+    let start = dict
+        .find(&format!("'{}'", key))
+        .or_else(|| dict.find(&format!("\"{}\"", key)))
+        .ok_or_else(missing)?;
+    let rest = dict
+        .get(start + key.len() + 2..)
+        .ok_or_else(missing)?
+        .trim_start();
+    let rest = rest.strip_prefix(':').ok_or_else(missing)?.trim_start();
+
+    let end = match rest.chars().next() {
+        Some(q @ ('\'' | '"')) => rest.get(1..).and_then(|r| r.find(q)).map(|i| i + 2),
+        Some('(') => rest.find(')').map(|i| i + 1),
+        Some('[') => rest.find(']').map(|i| i + 1),
+        Some(_) => rest.find([',', '}']),
+        None => None,
+    }
+    .ok_or_else(missing)?;
+
+    Ok(rest.get(..end).ok_or_else(missing)?.trim())
+}
@@ -0,0 +1,153 @@
+use crate::arr::Arr;
+use crate::io::{NpzReader, NpzWriter};
+use crate::mat::Mat;
+use num_complex::{Complex32, Complex64};
+
+#[test]
+fn test_npy_arr() {
+    let a = Arr::<f64>::with_vec(vec![1.0, -2.5, 3.25]);
+    let mut buf = Vec::new();
+    a.write_npy(&mut buf).unwrap();
+
+    assert_eq!(&buf[..8], b"\x93NUMPY\x01\x00");
+    assert_eq!((buf.len() - 3 * 8) % 64, 0);
+    let header = String::from_utf8_lossy(&buf[10..buf.len() - 24]);
+    assert_eq!(
+        header.trim_end(),
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }"
+    );
+
+    let b = Arr::<f64>::read_npy(buf.as_slice()).unwrap();
+    assert_eq!(b.values(), a.values());
+}
+
+#[test]
+fn test_npy_dtypes() {
+    let a = Arr::<i32>::with_vec(vec![-1, 0, 7]);
+    let mut buf = Vec::new();
+    a.write_npy(&mut buf).unwrap();
+    assert_eq!(
+        Arr::<i32>::read_npy(buf.as_slice()).unwrap().values(),
+        a.values()
+    );
+    assert!(Arr::<i64>::read_npy(buf.as_slice()).is_err());
+
+    let a = Arr::<u8>::with_vec(vec![1, 2, 255]);
+    let mut buf = Vec::new();
+    a.write_npy(&mut buf).unwrap();
+    assert!(String::from_utf8_lossy(&buf).contains("'|u1'"));
+    assert_eq!(
+        Arr::<u8>::read_npy(buf.as_slice()).unwrap().values(),
+        a.values()
+    );
+
+    let a = Arr::<Complex32>::with_vec(vec![Complex32::new(1.0, 2.0)]);
+    let mut buf = Vec::new();
+    a.write_npy(&mut buf).unwrap();
+    assert!(String::from_utf8_lossy(&buf).contains("'<c8'"));
+    assert_eq!(
+        Arr::<Complex32>::read_npy(buf.as_slice()).unwrap().values(),
+        a.values()
+    );
+}
+
+#[test]
+fn test_npy_big_endian() {
+    let header = "{'descr': '>c16', 'fortran_order': False, 'shape': (1,), }";
+    let mut buf = b"\x93NUMPY\x01\x00".to_vec();
+    buf.extend_from_slice(&(header.len() as u16 + 1).to_le_bytes());
+    buf.extend_from_slice(header.as_bytes());
+    buf.push(b'\n');
+    buf.extend_from_slice(&1.5f64.to_be_bytes());
+    buf.extend_from_slice(&(-2.0f64).to_be_bytes());
+
+    let a = Arr::<Complex64>::read_npy(buf.as_slice()).unwrap();
+    assert_eq!(a.values(), &[Complex64::new(1.5, -2.0)]);
+}
+
+#[test]
+fn test_npy_mat_order() {
+    let rows = 2;
+    let cols = 3;
+    for col_major in [false, true] {
+        let mut m = Mat::<f32>::zeros(rows, cols, col_major);
+        for r in 0..rows {
+            for c in 0..cols {
+                m[(r, c)] = (10 * r + c) as f32;
+            }
+        }
+        let mut buf = Vec::new();
+        m.write_npy(&mut buf).unwrap();
+        let fortran = if col_major { "True" } else { "False" };
+        assert!(String::from_utf8_lossy(&buf).contains(&format!("'fortran_order': {}", fortran)));
+
+        let n = Mat::<f32>::read_npy(buf.as_slice()).unwrap();
+        assert_eq!(n.shape(), (rows, cols));
+        assert_eq!(n.col_major(), col_major);
+        assert_eq!(n.values(), m.values());
+        assert!(Arr::<f32>::read_npy(buf.as_slice()).is_err());
+    }
+}
+
+#[test]
+fn test_npz() {
+    let a = Arr::<f64>::linspace(0.0, 1.0, 5, true);
+    let m = Mat::<Complex64>::identity(3, true);
+
+    let mut w = NpzWriter::new(Vec::new());
+    w.add_arr("a", &a).unwrap();
+    w.add_mat("m", &m).unwrap();
+    w.add_npy("scalar", &[], false, &[42i64]).unwrap();
+    let buf = w.finish().unwrap();
+
+    let r = NpzReader::new(buf.as_slice()).unwrap();
+    assert_eq!(r.names(), vec!["a", "m", "scalar"]);
+    assert_eq!(r.arr::<f64>("a").unwrap().values(), a.values());
+    let n = r.mat::<Complex64>("m").unwrap();
+    assert!(n.col_major());
+    assert_eq!(n.values(), m.values());
+    assert_eq!(r.npy::<i64>("scalar").unwrap(), (vec![], false, vec![42]));
+    assert!(r.arr::<f64>("missing").is_err());
+}
+
+#[test]
+fn test_npy_malformed_header() {
+    for header in [
+        "{'descr': ', 'fortran_order': False, 'shape': (1,), }",
+        "{'descr': '', 'fortran_order': False, 'shape': (1,), }",
+        "{'descr': '<', 'fortran_order': False, 'shape': (1,), }",
+        "{'descr': 'f8, 'fortran_order': False, 'shape': (1,), }",
+        "{'descr'",
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (1,",
+    ] {
+        let mut buf = b"\x93NUMPY\x01\x00".to_vec();
+        buf.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        buf.extend_from_slice(header.as_bytes());
+        buf.extend_from_slice(&1.0f64.to_le_bytes());
+        assert!(Arr::<f64>::read_npy(buf.as_slice()).is_err(), "{}", header);
+    }
+
+    // Shapes whose size overflows, or that exceed the data, are
+    // rejected without allocating for them.
+    for shape in ["(4294967296, 4294967296)", "(1000000000000, 1)", "(2, 1)"] {
+        let header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+            shape
+        );
+        let mut buf = b"\x93NUMPY\x01\x00".to_vec();
+        buf.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        buf.extend_from_slice(header.as_bytes());
+        buf.extend_from_slice(&1.0f64.to_le_bytes());
+        assert!(Mat::<f64>::read_npy(buf.as_slice()).is_err(), "{}", shape);
+    }
+}
+
+#[test]
+fn test_npz_entry_limit() {
+    let a = Arr::<u8>::new();
+    let mut w = NpzWriter::new(std::io::sink());
+    for i in 0..u16::MAX {
+        w.add_arr(&i.to_string(), &a).unwrap();
+    }
+    assert!(w.add_arr("last", &a).is_err());
+}
@@ -0,0 +1,302 @@
+use crate::arr::Arr;
+use crate::io::dtype::Element;
+use crate::io::invalid_data;
+use crate::io::npy::{read_npy, write_npy};
+use crate::mat::Mat;
+
+use std::io::{self, Read, Write};
+
+const LOCAL_HEADER: u32 = 0x04034b50;
+const CENTRAL_HEADER: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIR: u32 = 0x06054b50;
+const ZIP64_END_OF_CENTRAL_DIR: u32 = 0x06064b50;
+const ZIP64_LOCATOR: u32 = 0x07064b50;
+const ZIP64_EXTRA: u16 = 0x0001;
+
+// MS-DOS date for 1980-01-01.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Writes named arrays to a NumPy `.npz` archive.
+///
+/// Entries are stored uncompressed, as by `numpy.savez`.
+pub struct NpzWriter<W: Write> {
+    w: W,
+    offset: usize,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    crc: u32,
+    size: usize,
+    offset: usize,
+}
+
+impl<W: Write> NpzWriter<W> {
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds a one-dimensional array to the archive.
+    pub fn add_arr<T: Element>(&mut self, name: &str, a: &Arr<T>) -> io::Result<()> {
+        let mut buf = Vec::new();
+        a.write_npy(&mut buf)?;
+        self.add(name, &buf)
+    }
+
+    /// Adds a matrix to the archive.
+    pub fn add_mat<T: Element>(&mut self, name: &str, m: &Mat<T>) -> io::Result<()> {
+        let mut buf = Vec::new();
+        m.write_npy(&mut buf)?;
+        self.add(name, &buf)
+    }
+
+    /// Adds an array of any dimension to the archive.
+    pub fn add_npy<T: Element>(
+        &mut self,
+        name: &str,
+        shape: &[usize],
+        fortran_order: bool,
+        values: &[T],
+    ) -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_npy(&mut buf, shape, fortran_order, values)?;
+        self.add(name, &buf)
+    }
+
+    fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let name = format!("{}.npy", name);
+        if self.offset + data.len() > u32::MAX as usize {
+            return Err(invalid_data("npz archive exceeds 4 GiB"));
+        }
+        if self.entries.len() >= u16::MAX as usize {
+            return Err(invalid_data("npz archive exceeds 65535 entries"));
+        }
+        if name.len() > u16::MAX as usize {
+            return Err(invalid_data("npz entry name is too long"));
+        }
+        let entry = Entry {
+            crc: crc32fast::hash(data),
+            size: data.len(),
+            offset: self.offset,
+            name,
+        };
+
+        let mut h = Vec::with_capacity(30 + entry.name.len());
+        h.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+        h.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        h.extend_from_slice(&0u16.to_le_bytes()); // flags
+        h.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        h.extend_from_slice(&0u16.to_le_bytes()); // time
+        h.extend_from_slice(&DOS_DATE.to_le_bytes());
+        h.extend_from_slice(&entry.crc.to_le_bytes());
+        h.extend_from_slice(&(entry.size as u32).to_le_bytes());
+        h.extend_from_slice(&(entry.size as u32).to_le_bytes());
+        h.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+        h.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        h.extend_from_slice(entry.name.as_bytes());
+
+        self.w.write_all(&h)?;
+        self.w.write_all(data)?;
+        self.offset += h.len() + data.len();
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Writes the central directory and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut cd = Vec::new();
+        for entry in &self.entries {
+            cd.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+            cd.extend_from_slice(&20u16.to_le_bytes()); // version made by
+            cd.extend_from_slice(&20u16.to_le_bytes()); // version needed
+            cd.extend_from_slice(&0u16.to_le_bytes()); // flags
+            cd.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+            cd.extend_from_slice(&0u16.to_le_bytes()); // time
+            cd.extend_from_slice(&DOS_DATE.to_le_bytes());
+            cd.extend_from_slice(&entry.crc.to_le_bytes());
+            cd.extend_from_slice(&(entry.size as u32).to_le_bytes());
+            cd.extend_from_slice(&(entry.size as u32).to_le_bytes());
+            cd.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            cd.extend_from_slice(&0u16.to_le_bytes()); // extra length
+            cd.extend_from_slice(&0u16.to_le_bytes()); // comment length
+            cd.extend_from_slice(&0u16.to_le_bytes()); // disk number
+            cd.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+            cd.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+            cd.extend_from_slice(&(entry.offset as u32).to_le_bytes());
+            cd.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let n = self.entries.len() as u16;
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&END_OF_CENTRAL_DIR.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        end.extend_from_slice(&0u16.to_le_bytes()); // central directory disk
+        end.extend_from_slice(&n.to_le_bytes());
+        end.extend_from_slice(&n.to_le_bytes());
+        end.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+        end.extend_from_slice(&(self.offset as u32).to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+        self.w.write_all(&cd)?;
+        self.w.write_all(&end)?;
+        self.w.flush()?;
+        Ok(self.w)
+    }
+}
+
+/// Reads named arrays from a NumPy `.npz` archive.
+///
+/// Only uncompressed (stored) entries are supported,
+/// as written by `numpy.savez`.
+pub struct NpzReader {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl NpzReader {
+    /// Reads the complete archive from `r`.
+    pub fn new<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        Ok(Self {
+            entries: read_entries(&buf)?,
+        })
+    }
+
+    /// Returns the names of the arrays in the archive.
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Reads the named one-dimensional array.
+    pub fn arr<T: Element>(&self, name: &str) -> io::Result<Arr<T>> {
+        Arr::read_npy(self.entry(name)?)
+    }
+
+    /// Reads the named matrix.
+    pub fn mat<T: Element>(&self, name: &str) -> io::Result<Mat<T>> {
+        Mat::read_npy(self.entry(name)?)
+    }
+
+    /// Reads the named array of any dimension.
+    pub fn npy<T: Element>(&self, name: &str) -> io::Result<(Vec<usize>, bool, Vec<T>)> {
+        read_npy(self.entry(name)?)
+    }
+
+    fn entry(&self, name: &str) -> io::Result<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, data)| data.as_slice())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("array {:?} not found in npz archive", name),
+                )
+            })
+    }
+}
+
+fn bytes(buf: &[u8], pos: usize, n: usize) -> io::Result<&[u8]> {
+    buf.get(pos..pos + n)
+        .ok_or_else(|| invalid_data("truncated zip archive"))
+}
+
+fn u16_at(buf: &[u8], pos: usize) -> io::Result<u16> {
+    Ok(u16::from_le_bytes(bytes(buf, pos, 2)?.try_into().unwrap()))
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> io::Result<u32> {
+    Ok(u32::from_le_bytes(bytes(buf, pos, 4)?.try_into().unwrap()))
+}
+
+fn u64_at(buf: &[u8], pos: usize) -> io::Result<u64> {
+    Ok(u64::from_le_bytes(bytes(buf, pos, 8)?.try_into().unwrap()))
+}
+
+/// Returns the names (without `.npy` extension) and contents of
+/// the files in a zip archive.
+fn read_entries(buf: &[u8]) -> io::Result<Vec<(String, Vec<u8>)>> {
+    // The end of central directory record is followed by a
+    // comment of at most u16::MAX bytes.
+    let end = (0..=buf.len().saturating_sub(22))
+        .rev()
+        .take(u16::MAX as usize + 1)
+        .find(|&pos| u32_at(buf, pos).ok() == Some(END_OF_CENTRAL_DIR))
+        .ok_or_else(|| invalid_data("not a zip archive"))?;
+
+    let mut count = u16_at(buf, end + 10)? as u64;
+    let mut cd_offset = u32_at(buf, end + 16)? as u64;
+    if end >= 20 && u32_at(buf, end - 20)? == ZIP64_LOCATOR {
+        let end64 = u64_at(buf, end - 20 + 8)? as usize;
+        if u32_at(buf, end64)? != ZIP64_END_OF_CENTRAL_DIR {
+            return Err(invalid_data("invalid zip64 end of central directory"));
+        }
+        count = u64_at(buf, end64 + 32)?;
+        cd_offset = u64_at(buf, end64 + 48)?;
+    }
+
+    let mut entries = Vec::new();
+    let mut pos = cd_offset as usize;
+    for _ in 0..count {
+        if u32_at(buf, pos)? != CENTRAL_HEADER {
+            return Err(invalid_data("invalid zip central directory"));
+        }
+        let method = u16_at(buf, pos + 10)?;
+        let crc = u32_at(buf, pos + 16)?;
+        let mut compressed_size = u32_at(buf, pos + 20)? as u64;
+        let mut uncompressed_size = u32_at(buf, pos + 24)? as u64;
+        let name_len = u16_at(buf, pos + 28)? as usize;
+        let extra_len = u16_at(buf, pos + 30)? as usize;
+        let comment_len = u16_at(buf, pos + 32)? as usize;
+        let mut offset = u32_at(buf, pos + 42)? as u64;
+        let name = String::from_utf8_lossy(bytes(buf, pos + 46, name_len)?).into_owned();
+
+        // Sizes and offset that overflow 32 bits are stored, in order,
+        // in the zip64 extended information extra field.
+        let extra = bytes(buf, pos + 46 + name_len, extra_len)?;
+        let mut i = 0;
+        while i + 4 <= extra.len() {
+            let id = u16_at(extra, i)?;
+            let len = u16_at(extra, i + 2)? as usize;
+            if id == ZIP64_EXTRA {
+                let mut j = i + 4;
+                for v in [&mut uncompressed_size, &mut compressed_size, &mut offset] {
+                    if *v == u32::MAX as u64 {
+                        *v = u64_at(extra, j)?;
+                        j += 8;
+                    }
+                }
+            }
+            i += 4 + len;
+        }
+
+        if method != 0 {
+            return Err(invalid_data(format!(
+                "compressed zip entry {:?} is not supported",
+                name
+            )));
+        }
+
+        let local = offset as usize;
+        if u32_at(buf, local)? != LOCAL_HEADER {
+            return Err(invalid_data("invalid zip local file header"));
+        }
+        let data_offset =
+            local + 30 + u16_at(buf, local + 26)? as usize + u16_at(buf, local + 28)? as usize;
+        let data = bytes(buf, data_offset, compressed_size as usize)?;
+        if crc32fast::hash(data) != crc {
+            return Err(invalid_data(format!("crc mismatch for {:?}", name)));
+        }
+
+        let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+        entries.push((name, data.to_vec()));
+
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
@@ -0,0 +1,269 @@
+use crate::arr::Arr;
+use crate::io::dtype::{decode_le, encode_le, DType, Element};
+use crate::io::invalid_data;
+use crate::mat::{Mat, MatView};
+
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"FULLSNAP";
+const VERSION: u8 = 1;
+
+// Length of the header. The data that follows is aligned
+// to this many bytes relative to the start of the file.
+const HEADER_LEN: usize = 32;
+
+// Number of values encoded per write.
+const CHUNK_LEN: usize = 8192;
+
+/// Header of a snapshot file.
+///
+/// ```text
+/// 0..8    magic "FULLSNAP"
+/// 8       version
+/// 9       element type code
+/// 10      number of dimensions (1 or 2)
+/// 11      1 if column-major, else 0
+/// 12..16  reserved
+/// 16..24  rows (u64)
+/// 24..32  columns (u64)
+/// ```
+///
+/// The header is followed by the little-endian element values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    pub dtype: DType,
+    pub ndim: usize,
+    pub rows: usize,
+    pub cols: usize,
+    pub col_major: bool,
+}
+
+impl SnapshotHeader {
+    fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut h = [0; HEADER_LEN];
+        h[..8].copy_from_slice(MAGIC);
+        h[8] = VERSION;
+        h[9] = self.dtype.code();
+        h[10] = self.ndim as u8;
+        h[11] = self.col_major as u8;
+        h[16..24].copy_from_slice(&(self.rows as u64).to_le_bytes());
+        h[24..32].copy_from_slice(&(self.cols as u64).to_le_bytes());
+        h
+    }
+
+    fn decode(h: &[u8]) -> io::Result<Self> {
+        if h.len() < HEADER_LEN || &h[..8] != MAGIC {
+            return Err(invalid_data("not a snapshot file"));
+        }
+        if h[8] != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported snapshot version {}",
+                h[8]
+            )));
+        }
+        let dtype = DType::from_code(h[9])
+            .ok_or_else(|| invalid_data(format!("unknown element type code {}", h[9])))?;
+        let dim = |b: &[u8]| usize::try_from(u64::from_le_bytes(b.try_into().unwrap())).ok();
+        let too_large = || invalid_data("snapshot too large");
+        let rows = dim(&h[16..24]).ok_or_else(too_large)?;
+        let cols = dim(&h[24..32]).ok_or_else(too_large)?;
+        rows.checked_mul(cols)
+            .and_then(|n| n.checked_mul(dtype.size()))
+            .ok_or_else(too_large)?;
+        Ok(Self {
+            dtype,
+            ndim: h[10] as usize,
+            rows,
+            cols,
+            col_major: h[11] != 0,
+        })
+    }
+
+    /// Returns the number of element values, saturating at `usize::MAX`.
+    /// Decoded headers are checked not to overflow.
+    pub fn len(&self) -> usize {
+        self.rows.saturating_mul(self.cols)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check<T: Element>(&self, ndim: usize) -> io::Result<()> {
+        if self.dtype != T::DTYPE {
+            return Err(invalid_data(format!(
+                "element type mismatch: snapshot has {:?}, expected {:?}",
+                self.dtype,
+                T::DTYPE
+            )));
+        }
+        if self.ndim != ndim {
+            return Err(invalid_data(format!(
+                "snapshot has {} dimensions, expected {}",
+                self.ndim, ndim
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<T> Arr<T>
+where
+    T: Element,
+{
+    /// Writes the array in snapshot format.
+    pub fn write_snapshot<W: Write>(&self, w: W) -> io::Result<()> {
+        let header = SnapshotHeader {
+            dtype: T::DTYPE,
+            ndim: 1,
+            rows: 1,
+            cols: self.values.len(),
+            col_major: false,
+        };
+        write_snapshot(w, &header, &self.values)
+    }
+
+    /// Reads an array in snapshot format.
+    pub fn read_snapshot<R: Read>(r: R) -> io::Result<Self> {
+        let (header, values) = read_snapshot(r)?;
+        header.check::<T>(1)?;
+        Ok(Self { values })
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Element,
+{
+    /// Writes the matrix in snapshot format.
+    pub fn write_snapshot<W: Write>(&self, w: W) -> io::Result<()> {
+        let header = SnapshotHeader {
+            dtype: T::DTYPE,
+            ndim: 2,
+            rows: self.rows,
+            cols: self.cols,
+            col_major: self.col_major,
+        };
+        write_snapshot(w, &header, &self.values)
+    }
+
+    /// Reads a matrix in snapshot format.
+    pub fn read_snapshot<R: Read>(r: R) -> io::Result<Self> {
+        let (header, values) = read_snapshot(r)?;
+        header.check::<T>(2)?;
+        Ok(Mat::new(header.rows, header.cols, values, header.col_major))
+    }
+}
+
+fn write_snapshot<T, W>(mut w: W, header: &SnapshotHeader, values: &[T]) -> io::Result<()>
+where
+    T: Element,
+    W: Write,
+{
+    w.write_all(&header.encode())?;
+    for chunk in values.chunks(CHUNK_LEN) {
+        w.write_all(&encode_le(chunk))?;
+    }
+    w.flush()
+}
+
+fn read_snapshot<T, R>(mut r: R) -> io::Result<(SnapshotHeader, Vec<T>)>
+where
+    T: Element,
+    R: Read,
+{
+    let mut h = [0; HEADER_LEN];
+    r.read_exact(&mut h)?;
+    let header = SnapshotHeader::decode(&h)?;
+    if header.dtype != T::DTYPE {
+        return Err(invalid_data(format!(
+            "element type mismatch: snapshot has {:?}, expected {:?}",
+            header.dtype,
+            T::DTYPE
+        )));
+    }
+
+    // The length of the input is unknown, so grow the values as they are
+    // read rather than trusting the header with a single allocation.
+    let mut values = Vec::with_capacity(header.len().min(CHUNK_LEN));
+    let mut buf = vec![0; CHUNK_LEN * T::DTYPE.size()];
+    while values.len() < header.len() {
+        let n = (header.len() - values.len()).min(CHUNK_LEN);
+        let buf = &mut buf[..n * T::DTYPE.size()];
+        r.read_exact(buf)?;
+        values.extend(decode_le::<T>(buf));
+    }
+    Ok((header, values))
+}
+
+/// A memory-mapped snapshot file.
+///
+/// The element values are borrowed directly from the mapping without
+/// being copied or parsed. This requires a little-endian host.
+pub struct Snapshot {
+    header: SnapshotHeader,
+    mmap: Mmap,
+}
+
+impl Snapshot {
+    /// Memory maps the snapshot file at `path`.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // Safety: the mapping is read-only and the caller is
+        // responsible for not modifying the file concurrently.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let header = SnapshotHeader::decode(&mmap)?;
+        let n = header
+            .len()
+            .checked_mul(header.dtype.size())
+            .ok_or_else(|| invalid_data("snapshot too large"))?;
+        if mmap.len() < HEADER_LEN + n {
+            return Err(invalid_data("truncated snapshot file"));
+        }
+        Ok(Self { header, mmap })
+    }
+
+    pub fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+
+    /// Returns the values of a one-dimensional snapshot.
+    pub fn arr<T: Element>(&self) -> io::Result<&[T]> {
+        self.header.check::<T>(1)?;
+        self.values()
+    }
+
+    /// Returns a view of a two-dimensional snapshot.
+    pub fn mat<T: Element>(&self) -> io::Result<MatView<'_, T>> {
+        self.header.check::<T>(2)?;
+        Ok(MatView::new(
+            self.header.rows,
+            self.header.cols,
+            self.values()?,
+            self.header.col_major,
+        ))
+    }
+
+    fn values<T: Element>(&self) -> io::Result<&[T]> {
+        if cfg!(target_endian = "big") {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "snapshot views require a little-endian host",
+            ));
+        }
+        let data = &self.mmap[HEADER_LEN..HEADER_LEN + self.header.len() * T::DTYPE.size()];
+        // Safety: `Element` is sealed and only implemented for plain
+        // old data types whose little-endian encoding is their memory
+        // representation, so any bit pattern is a valid value.
+        let (prefix, values, suffix) = unsafe { data.align_to::<T>() };
+        if !prefix.is_empty() || !suffix.is_empty() {
+            return Err(invalid_data("misaligned snapshot data"));
+        }
+        Ok(values)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::arr::Arr;
+use crate::io::{DType, Snapshot};
+use crate::mat::Mat;
+use num_complex::Complex64;
+use std::fs::File;
+
+#[test]
+fn test_snapshot_read_write() {
+    let a = Arr::<i16>::with_vec(vec![1, -2, 3]);
+    let mut buf = Vec::new();
+    a.write_snapshot(&mut buf).unwrap();
+    assert_eq!(buf.len(), 32 + 3 * 2);
+    assert_eq!(
+        Arr::<i16>::read_snapshot(buf.as_slice()).unwrap().values(),
+        a.values()
+    );
+    assert!(Arr::<u16>::read_snapshot(buf.as_slice()).is_err());
+    assert!(Mat::<i16>::read_snapshot(buf.as_slice()).is_err());
+
+    let mut buf = Vec::new();
+    Mat::new(2, 20_000, Arr::<f64>::range(40_000).vec(), true)
+        .write_snapshot(&mut buf)
+        .unwrap();
+    let n = Mat::<f64>::read_snapshot(buf.as_slice()).unwrap();
+    assert_eq!(n.shape(), (2, 20_000));
+    assert!(n.col_major());
+    assert_eq!(n[(1, 19_999)], 39_999.0);
+}
+
+#[test]
+fn test_snapshot_mmap() {
+    let mut m = Mat::<Complex64>::zeros(3, 2, true);
+    m[(2, 1)] = Complex64::new(1.0, -1.0);
+    let a = Arr::<f32>::linspace(0.0, 1.0, 11, true);
+
+    let dir = std::env::temp_dir();
+    let mat_path = dir.join(format!("full_snapshot_mat_{}.bin", std::process::id()));
+    let arr_path = dir.join(format!("full_snapshot_arr_{}.bin", std::process::id()));
+    m.write_snapshot(File::create(&mat_path).unwrap()).unwrap();
+    a.write_snapshot(File::create(&arr_path).unwrap()).unwrap();
+
+    let s = Snapshot::open(&mat_path).unwrap();
+    assert_eq!(s.header().dtype, DType::C128);
+    let v = s.mat::<Complex64>().unwrap();
+    assert_eq!(v.shape(), (3, 2));
+    assert!(v.col_major());
+    assert_eq!(v[(2, 1)], Complex64::new(1.0, -1.0));
+    assert_eq!(v.to_mat().values(), m.values());
+    assert!(s.arr::<Complex64>().is_err());
+    assert!(s.mat::<f64>().is_err());
+
+    let s = Snapshot::open(&arr_path).unwrap();
+    assert_eq!(s.arr::<f32>().unwrap(), a.values());
+
+    std::fs::remove_file(mat_path).unwrap();
+    std::fs::remove_file(arr_path).unwrap();
+}
+
+#[test]
+fn test_snapshot_corrupt_header() {
+    let mut buf = Vec::new();
+    Mat::<f64>::zeros(2, 2, false)
+        .write_snapshot(&mut buf)
+        .unwrap();
+
+    // Dimensions whose product overflows.
+    let mut bad = buf.clone();
+    bad[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+    bad[24..32].copy_from_slice(&2u64.to_le_bytes());
+    assert!(Mat::<f64>::read_snapshot(bad.as_slice()).is_err());
+
+    // A huge length with little data fails on reading, not allocating.
+    let mut bad = buf.clone();
+    bad[16..24].copy_from_slice(&(1u64 << 40).to_le_bytes());
+    bad[24..32].copy_from_slice(&1u64.to_le_bytes());
+    assert!(Mat::<f64>::read_snapshot(bad.as_slice()).is_err());
+
+    let path = std::env::temp_dir().join(format!("full_snapshot_bad_{}.bin", std::process::id()));
+    std::fs::write(&path, &bad).unwrap();
+    assert!(Snapshot::open(&path).is_err());
+    std::fs::remove_file(path).unwrap();
+}
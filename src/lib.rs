@@ -1,11 +1,35 @@
+#[cfg(feature = "approx")]
+mod approx_eq;
 mod arr;
+mod error;
+mod fft;
 mod full;
+mod integrate;
+mod interp;
+mod io;
+mod mask;
 mod mat;
+mod poly;
+mod signal;
+mod stats;
 mod traits;
 
 pub mod iter;
 pub mod slice;
 
+#[cfg(test)]
+mod mask_test;
+
 pub use arr::*;
+pub use error::*;
+pub use fft::*;
+pub use integrate::*;
+pub use interp::*;
+pub use io::*;
+pub use mask::*;
 pub use mat::*;
+pub use poly::*;
+pub use signal::*;
+pub use slice::Spacing;
+pub use stats::*;
 pub use traits::*;
@@ -0,0 +1,282 @@
+use crate::arr::Arr;
+use crate::mat::Mat;
+
+use num_traits::{One, Zero};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A bit-packed array of booleans, e.g. the result of an element-wise
+/// comparison. Masks of matrices are in row-major order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Mask {
+    // Bits beyond `len` are always zero.
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Mask {
+    /// Returns a mask of `len` false values.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(BITS)],
+            len,
+        }
+    }
+
+    /// Returns a mask with all `len` values set to `v`.
+    pub fn with_value(len: usize, v: bool) -> Self {
+        let mut mask = Self::new(len);
+        if v {
+            mask.words.fill(u64::MAX);
+            mask.clear_unused();
+        }
+        mask
+    }
+
+    /// Returns a mask with values `f(i)`.
+    pub fn from_fn(len: usize, f: impl FnMut(usize) -> bool) -> Self {
+        (0..len).map(f).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "index {} out of range for mask of length {}",
+            i,
+            self.len
+        );
+        self.words[i / BITS] & (1 << (i % BITS)) != 0
+    }
+
+    pub fn set(&mut self, i: usize, v: bool) {
+        assert!(
+            i < self.len,
+            "index {} out of range for mask of length {}",
+            i,
+            self.len
+        );
+        if v {
+            self.words[i / BITS] |= 1 << (i % BITS);
+        } else {
+            self.words[i / BITS] &= !(1 << (i % BITS));
+        }
+    }
+
+    /// Returns the number of true values.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns true if any values are true.
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&w| w != 0)
+    }
+
+    /// Returns true if all values are true.
+    pub fn all(&self) -> bool {
+        self.count() == self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    /// Returns the indexes of the true values.
+    pub fn indices(&self) -> Vec<usize> {
+        let mut ix = Vec::with_capacity(self.count());
+        for (k, &w) in self.words.iter().enumerate() {
+            let mut w = w;
+            while w != 0 {
+                ix.push(k * BITS + w.trailing_zeros() as usize);
+                w &= w - 1;
+            }
+        }
+        ix
+    }
+
+    /// Returns a vector with `1`s where the mask is true.
+    pub fn to_vec<U>(&self) -> Vec<U>
+    where
+        U: Zero + One,
+    {
+        self.iter()
+            .map(|v| if v { U::one() } else { U::zero() })
+            .collect()
+    }
+
+    fn clear_unused(&mut self) {
+        if !self.len.is_multiple_of(BITS) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % BITS)) - 1;
+            }
+        }
+    }
+
+    fn zip_words(&self, len: usize, words: &[u64], f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.len, len);
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            len: self.len,
+        }
+    }
+}
+
+impl FromIterator<bool> for Mask {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut words = Vec::new();
+        let mut len: usize = 0;
+        for v in iter {
+            if len.is_multiple_of(BITS) {
+                words.push(0);
+            }
+            if v {
+                words[len / BITS] |= 1 << (len % BITS);
+            }
+            len += 1;
+        }
+        Self { words, len }
+    }
+}
+
+impl From<&[bool]> for Mask {
+    fn from(v: &[bool]) -> Self {
+        v.iter().copied().collect()
+    }
+}
+
+impl From<Vec<bool>> for Mask {
+    fn from(v: Vec<bool>) -> Self {
+        v.into_iter().collect()
+    }
+}
+
+#[opimps::impl_ops(BitAnd)]
+fn bitand(self: Mask, rhs: Mask) -> Mask {
+    self.zip_words(rhs.len, &rhs.words, |a, b| a & b)
+}
+
+#[opimps::impl_ops(BitOr)]
+fn bitor(self: Mask, rhs: Mask) -> Mask {
+    self.zip_words(rhs.len, &rhs.words, |a, b| a | b)
+}
+
+#[opimps::impl_ops(BitXor)]
+fn bitxor(self: Mask, rhs: Mask) -> Mask {
+    self.zip_words(rhs.len, &rhs.words, |a, b| a ^ b)
+}
+
+#[opimps::impl_ops_assign(BitAndAssign)]
+fn bitand_assign(self: Mask, rhs: Mask) {
+    *self = self.zip_words(rhs.len, &rhs.words, |a, b| a & b);
+}
+
+#[opimps::impl_ops_assign(BitOrAssign)]
+fn bitor_assign(self: Mask, rhs: Mask) {
+    *self = self.zip_words(rhs.len, &rhs.words, |a, b| a | b);
+}
+
+#[opimps::impl_ops_assign(BitXorAssign)]
+fn bitxor_assign(self: Mask, rhs: Mask) {
+    *self = self.zip_words(rhs.len, &rhs.words, |a, b| a ^ b);
+}
+
+#[opimps::impl_uni_ops(Not)]
+fn not(self: Mask) -> Mask {
+    let mut mask = Mask {
+        words: self.words.iter().map(|w| !w).collect(),
+        len: self.len,
+    };
+    mask.clear_unused();
+    mask
+}
+
+/// The right-hand side of an element-wise comparison or selection:
+/// either a scalar, which is broadcast, or an array of matching shape.
+pub trait Operand<T> {
+    /// Returns the `(rows, cols)` shape, or `None` for a scalar.
+    /// Arrays and slices have shape `(1, len)`.
+    fn shape(&self) -> Option<(usize, usize)>;
+
+    /// Returns the element at row-major index `i`.
+    fn value(&self, i: usize) -> T;
+}
+
+impl<T: Copy> Operand<T> for T {
+    fn shape(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    fn value(&self, _i: usize) -> T {
+        *self
+    }
+}
+
+impl<T: Copy> Operand<T> for &[T] {
+    fn shape(&self) -> Option<(usize, usize)> {
+        Some((1, self.len()))
+    }
+
+    fn value(&self, i: usize) -> T {
+        self[i]
+    }
+}
+
+impl<T: Copy> Operand<T> for &Arr<T> {
+    fn shape(&self) -> Option<(usize, usize)> {
+        Some((1, self.len()))
+    }
+
+    fn value(&self, i: usize) -> T {
+        self.values[i]
+    }
+}
+
+impl<T: Copy> Operand<T> for &Mat<T> {
+    fn shape(&self) -> Option<(usize, usize)> {
+        Some((self.rows, self.cols))
+    }
+
+    fn value(&self, i: usize) -> T {
+        self[(i / self.cols, i % self.cols)]
+    }
+}
+
+/// Returns `a[i]` where `mask[i]` is true and `b[i]` elsewhere. Scalars are
+/// broadcast; array operands must have the length of `mask`.
+pub fn where_<T, A, B>(mask: &Mask, a: A, b: B) -> Arr<T>
+where
+    A: Operand<T>,
+    B: Operand<T>,
+{
+    let shape = (1, mask.len());
+    check_operand(shape, &a);
+    check_operand(shape, &b);
+    Arr {
+        values: mask
+            .iter()
+            .enumerate()
+            .map(|(i, m)| if m { a.value(i) } else { b.value(i) })
+            .collect(),
+    }
+}
+
+/// Panics if `rhs` is not a scalar and its shape is not `shape`.
+pub(crate) fn check_operand<T>(shape: (usize, usize), rhs: &impl Operand<T>) {
+    if let Some(s) = rhs.shape() {
+        assert_eq!(s, shape, "operand shape mismatch");
+    }
+}
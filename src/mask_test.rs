@@ -0,0 +1,92 @@
+use crate::arr::Arr;
+use crate::mask::{where_, Mask};
+use crate::mat::Mat;
+
+#[test]
+fn test_mask_ops() {
+    let a: Mask = (0..70).map(|i| i % 2 == 0).collect();
+    let b = Mask::from_fn(70, |i| i % 3 == 0);
+
+    assert_eq!(a.len(), 70);
+    assert_eq!(a.count(), 35);
+    assert!(a.get(68));
+    assert!(!a.get(69));
+
+    let c = &a & &b;
+    assert_eq!(c.indices(), (0..70).step_by(6).collect::<Vec<_>>());
+    assert_eq!((&a | &b).count(), 35 + 24 - 12);
+    assert_eq!((&a ^ &b).count(), 35 + 24 - 2 * 12);
+
+    let not_a = !&a;
+    assert_eq!(not_a.count(), 35);
+    assert_eq!(!not_a, a);
+
+    let mut d = a.clone();
+    d &= &b;
+    assert_eq!(d, c);
+    d |= Mask::with_value(70, true);
+    assert!(d.all());
+    d ^= Mask::with_value(70, true);
+    assert!(!d.any());
+
+    let e = Mask::from(vec![true, false, true]);
+    assert_eq!(e.to_vec::<f64>(), vec![1.0, 0.0, 1.0]);
+    assert_eq!(e.iter().collect::<Vec<_>>(), vec![true, false, true]);
+    assert!(Mask::new(0).all());
+    assert!(!Mask::new(0).any());
+}
+
+#[test]
+fn test_mask_index() {
+    let mut a = Arr::with_vec(vec![1.0, f64::NAN, 3.0, f64::NAN]);
+    let nan = a.is_nan();
+    assert_eq!(nan.indices(), vec![1, 3]);
+    assert_eq!(a.select_mask(&!&nan).vec(), vec![1.0, 3.0]);
+    a.set_where(&nan, 0.0);
+    assert_eq!(a.vec(), vec![1.0, 0.0, 3.0, 0.0]);
+
+    let mut m = Mat::new(2, 2, vec![1.0, f64::NAN, 3.0, 4.0], true);
+    let nan = m.is_nan();
+    assert_eq!(nan.indices(), vec![2]);
+    assert_eq!(m.select_mask(&!&nan).vec(), vec![1.0, 3.0, 4.0]);
+    m.set_where(&nan, -1.0);
+    assert_eq!(m[(1, 0)], -1.0);
+}
+
+#[test]
+fn test_compare() {
+    let a = Arr::with_vec(vec![1.0, 2.0, f64::NAN, 4.0]);
+    let b = Arr::with_vec(vec![4.0, 2.0, 0.0, 1.0]);
+
+    assert_eq!(a.gt(2.0).indices(), vec![3]);
+    assert_eq!(a.ge(2.0).indices(), vec![1, 3]);
+    assert_eq!(a.lt(&b).indices(), vec![0]);
+    assert_eq!(a.le(&b).indices(), vec![0, 1]);
+    assert_eq!(a.eq_mask(&b[..]).indices(), vec![1]);
+    assert_eq!(a.ne_mask(2.0).indices(), vec![0, 2, 3]);
+    // The trait methods are not shadowed.
+    assert!(b.eq(&b.clone()));
+    assert!(a.ne(&b));
+
+    let c = where_(&(a.gt(1.5) & a.lt(5.0)), &a, 0.0);
+    assert_eq!(c.vec(), vec![0.0, 2.0, 0.0, 4.0]);
+    assert_eq!(
+        where_(&a.is_nan(), -1.0, &b).vec(),
+        vec![4.0, 2.0, -1.0, 1.0]
+    );
+
+    let m = Mat::new(2, 2, vec![1.0, 3.0, 2.0, 4.0], true);
+    let n = Mat::new(2, 2, vec![0.0, 2.0, 5.0, 4.0], false);
+    assert_eq!(m.gt(1.5).indices(), vec![1, 2, 3]);
+    assert_eq!(m.eq_mask(&n).indices(), vec![1, 3]);
+
+    let w = Mat::where_(&m.lt(&n), &m, &n);
+    assert!(w == Mat::new(2, 2, vec![0.0, 2.0, 3.0, 4.0], false));
+}
+
+#[test]
+#[should_panic(expected = "operand shape mismatch")]
+fn test_compare_shape() {
+    let a = Arr::with_vec(vec![1.0, 2.0]);
+    a.gt(&Arr::with_vec(vec![1.0]));
+}
@@ -0,0 +1,235 @@
+use crate::arr::Arr;
+use crate::mat::Mat;
+use crate::slice::{
+    cumtrapz, gradient, kurtosis, median, mode, nanargmax, nanargmin, nanmax, nanmean, nanmin,
+    nansum, quantile, simpson, skewness, std, trapz, var, Spacing,
+};
+use crate::stats::QuantileMethod;
+use crate::traits::{Float, IsNaN, Sqrt};
+
+use num_traits::{FromPrimitive, Zero};
+use std::ops::{AddAssign, Div};
+
+/// Matrix axis along which an operation is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Down the rows of each column, giving one result
+    /// per column (NumPy `axis=0`).
+    Rows,
+    /// Across the columns of each row, giving one result
+    /// per row (NumPy `axis=1`).
+    Cols,
+}
+
+impl<T> Mat<T> {
+    /// Returns the number of lanes along `axis`: the
+    /// number of columns for `Rows` and rows for `Cols`.
+    pub fn lanes(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::Rows => self.cols,
+            Axis::Cols => self.rows,
+        }
+    }
+
+    /// Applies `f` to each column (`Axis::Rows`) or row (`Axis::Cols`).
+    /// Lanes that are not contiguous in storage are copied.
+    pub fn map_axis<U>(&self, axis: Axis, mut f: impl FnMut(&[T]) -> U) -> Vec<U>
+    where
+        T: Copy,
+    {
+        match (axis, self.col_major) {
+            (Axis::Rows, true) if self.rows != 0 => self.col_iter().map(f).collect(),
+            (Axis::Cols, false) if self.cols != 0 => self.row_iter().map(f).collect(),
+            (Axis::Rows, _) => (0..self.cols)
+                .map(|c| f(&self.col(c).copied().collect::<Vec<T>>()))
+                .collect(),
+            (Axis::Cols, _) => (0..self.rows)
+                .map(|r| f(&self.row(r).copied().collect::<Vec<T>>()))
+                .collect(),
+        }
+    }
+
+    // Returns the number of values in each lane along `axis`.
+    fn lane_len(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::Rows => self.rows,
+            Axis::Cols => self.cols,
+        }
+    }
+
+    // Builds a matrix from lanes of equal length `len` along `axis`, as
+    // returned by `map_axis`, storing the lanes contiguously.
+    fn collect_lanes(&self, axis: Axis, len: usize, lanes: Vec<Vec<T>>) -> Mat<T> {
+        let values = lanes.into_iter().flatten().collect();
+        match axis {
+            Axis::Rows => Mat::new(len, self.cols, values, true),
+            Axis::Cols => Mat::new(self.rows, len, values, false),
+        }
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Copy + IsNaN,
+{
+    /// Returns the sums along `axis` of the values that are not NaN.
+    pub fn nansum(&self, axis: Axis) -> Arr<T>
+    where
+        T: Zero + AddAssign,
+    {
+        Arr {
+            values: self.map_axis(axis, nansum),
+        }
+    }
+
+    /// Returns the means along `axis` of the values that are not NaN.
+    pub fn nanmean(&self, axis: Axis) -> Arr<T>
+    where
+        T: Zero + AddAssign + Div<Output = T> + FromPrimitive,
+    {
+        Arr {
+            values: self.map_axis(axis, nanmean),
+        }
+    }
+
+    /// Returns the maximum values along `axis` ignoring NaNs.
+    pub fn nanmax(&self, axis: Axis) -> Arr<T>
+    where
+        T: PartialOrd + Zero + Div<Output = T>,
+    {
+        Arr {
+            values: self.map_axis(axis, nanmax),
+        }
+    }
+
+    /// Returns the minimum values along `axis` ignoring NaNs.
+    pub fn nanmin(&self, axis: Axis) -> Arr<T>
+    where
+        T: PartialOrd + Zero + Div<Output = T>,
+    {
+        Arr {
+            values: self.map_axis(axis, nanmin),
+        }
+    }
+
+    /// Returns the indexes of the maximum values along `axis`
+    /// ignoring NaNs, `None` for lanes where all values are NaN.
+    pub fn nanargmax(&self, axis: Axis) -> Vec<Option<usize>>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, nanargmax)
+    }
+
+    /// Returns the indexes of the minimum values along `axis`
+    /// ignoring NaNs, `None` for lanes where all values are NaN.
+    pub fn nanargmin(&self, axis: Axis) -> Vec<Option<usize>>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, nanargmin)
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Float,
+{
+    /// Returns the variances along `axis` with `ddof` delta degrees of freedom.
+    pub fn var(&self, axis: Axis, ddof: usize) -> Arr<T>
+    where
+        T: AddAssign + FromPrimitive,
+    {
+        Arr {
+            values: self.map_axis(axis, |a| var(a, ddof)),
+        }
+    }
+
+    /// Returns the standard deviations along `axis` with `ddof` delta degrees of freedom.
+    pub fn std(&self, axis: Axis, ddof: usize) -> Arr<T>
+    where
+        T: AddAssign + FromPrimitive + Sqrt,
+    {
+        Arr {
+            values: self.map_axis(axis, |a| std(a, ddof)),
+        }
+    }
+
+    /// Returns the medians along `axis`.
+    pub fn median(&self, axis: Axis) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, median),
+        }
+    }
+
+    /// Returns quantile `q`, for `q` in `[0, 1]`, along `axis`.
+    pub fn quantile(&self, axis: Axis, q: f64, method: QuantileMethod) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, |a| quantile(a, q, method)),
+        }
+    }
+
+    /// Returns percentile `p`, for `p` in `[0, 100]`, along `axis`.
+    pub fn percentile(&self, axis: Axis, p: f64, method: QuantileMethod) -> Arr<T> {
+        self.quantile(axis, p / 100.0, method)
+    }
+
+    /// Returns the most frequent values along `axis`, the smallest if tied.
+    pub fn mode(&self, axis: Axis) -> Arr<T>
+    where
+        T: IsNaN,
+    {
+        Arr {
+            values: self.map_axis(axis, mode),
+        }
+    }
+
+    /// Returns the biased sample skewness along `axis`.
+    pub fn skewness(&self, axis: Axis) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, skewness),
+        }
+    }
+
+    /// Returns the biased sample excess kurtosis along `axis`.
+    pub fn kurtosis(&self, axis: Axis) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, kurtosis),
+        }
+    }
+
+    /// Integrates along `axis` using the trapezoidal rule, with the spacing
+    /// of the rows (`Axis::Rows`) or columns (`Axis::Cols`).
+    pub fn trapz(&self, axis: Axis, spacing: Spacing<T>) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, |a| trapz(a, spacing)),
+        }
+    }
+
+    /// Returns the cumulative trapezoidal integrals along `axis`. The result
+    /// is one shorter along `axis` unless `initial` is given, and empty if
+    /// `self` is empty along `axis`.
+    pub fn cumtrapz(&self, axis: Axis, spacing: Spacing<T>, initial: Option<T>) -> Mat<T> {
+        let n = self.lane_len(axis);
+        let len = if initial.is_some() {
+            n
+        } else {
+            n.saturating_sub(1)
+        };
+        let lanes = self.map_axis(axis, |a| cumtrapz(a, spacing, initial));
+        self.collect_lanes(axis, len, lanes)
+    }
+
+    /// Integrates along `axis` using Simpson's rule.
+    pub fn simpson(&self, axis: Axis, spacing: Spacing<T>) -> Arr<T> {
+        Arr {
+            values: self.map_axis(axis, |a| simpson(a, spacing)),
+        }
+    }
+
+    /// Returns the gradient along `axis`. See [`crate::slice::gradient`].
+    pub fn gradient(&self, axis: Axis, spacing: Spacing<T>, edge_order: usize) -> Mat<T> {
+        let lanes = self.map_axis(axis, |a| gradient(a, spacing, edge_order));
+        self.collect_lanes(axis, self.lane_len(axis), lanes)
+    }
+}
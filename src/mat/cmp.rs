@@ -0,0 +1,102 @@
+use crate::mask::{check_operand, Mask, Operand};
+use crate::mat::Mat;
+
+/// Element-wise comparisons, returning row-major masks. The right-hand
+/// side may be a scalar, which is broadcast, or a matrix of the same
+/// shape. Comparisons with NaN are false, except `ne_mask`.
+///
+/// As for `Arr`, equality is named `eq_mask` and `ne_mask` so that it does
+/// not shadow `PartialEq`.
+impl<T> Mat<T>
+where
+    T: Copy,
+{
+    fn compare<R>(&self, rhs: R, f: impl Fn(&T, &T) -> bool) -> Mask
+    where
+        R: Operand<T>,
+    {
+        check_operand((self.rows, self.cols), &rhs);
+        Mask::from_fn(self.rows * self.cols, |i| {
+            f(&self[(i / self.cols, i % self.cols)], &rhs.value(i))
+        })
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] > rhs[(r, c)]`.
+    pub fn gt<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a > b)
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] >= rhs[(r, c)]`.
+    pub fn ge<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a >= b)
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] < rhs[(r, c)]`.
+    pub fn lt<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a < b)
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] <= rhs[(r, c)]`.
+    pub fn le<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialOrd,
+    {
+        self.compare(rhs, |a, b| a <= b)
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] == rhs[(r, c)]`.
+    pub fn eq_mask<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialEq,
+    {
+        self.compare(rhs, |a, b| a == b)
+    }
+
+    /// Returns a mask that is true where `self[(r, c)] != rhs[(r, c)]`.
+    pub fn ne_mask<R: Operand<T>>(&self, rhs: R) -> Mask
+    where
+        T: PartialEq,
+    {
+        self.compare(rhs, |a, b| a != b)
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Copy,
+{
+    /// Returns a row-major matrix of `a[(r, c)]` where `mask` is true and
+    /// `b[(r, c)]` elsewhere. At least one operand must be a matrix.
+    pub fn where_<A, B>(mask: &Mask, a: A, b: B) -> Self
+    where
+        A: Operand<T>,
+        B: Operand<T>,
+    {
+        let (rows, cols) = a
+            .shape()
+            .or(b.shape())
+            .expect("where_ requires a matrix operand");
+        assert_eq!(mask.len(), rows * cols);
+        check_operand((rows, cols), &a);
+        check_operand((rows, cols), &b);
+        Self {
+            rows,
+            cols,
+            values: mask
+                .iter()
+                .enumerate()
+                .map(|(i, m)| if m { a.value(i) } else { b.value(i) })
+                .collect(),
+            col_major: false,
+        }
+    }
+}
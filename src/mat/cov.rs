@@ -0,0 +1,92 @@
+use crate::mat::{Axis, Mat};
+use crate::traits::Float;
+
+impl<T> Mat<T>
+where
+    T: Float,
+{
+    /// Returns the covariance matrix of the variables in the rows
+    /// (`rowvar`) or columns of `self`, normalized by `N - ddof` where
+    /// `N` is the number of observations.
+    pub fn cov(&self, rowvar: bool, ddof: usize) -> Mat<T> {
+        self.cov_weighted(rowvar, ddof, None, None)
+    }
+
+    /// Returns the weighted covariance matrix, with integer frequency
+    /// weights `fweights` and/or observation weights `aweights`, as
+    /// defined by NumPy's `cov`.
+    pub fn cov_weighted(
+        &self,
+        rowvar: bool,
+        ddof: usize,
+        fweights: Option<&[T]>,
+        aweights: Option<&[T]>,
+    ) -> Mat<T> {
+        let axis = if rowvar { Axis::Cols } else { Axis::Rows };
+        let n = self.lanes(if rowvar { Axis::Rows } else { Axis::Cols });
+
+        let mut w = vec![T::one(); n];
+        for weights in [fweights, aweights].into_iter().flatten() {
+            assert_eq!(
+                weights.len(),
+                n,
+                "weights must have one value per observation"
+            );
+            assert!(
+                weights.iter().all(|&v| v >= T::zero()),
+                "weights must be non-negative"
+            );
+            w.iter_mut().zip(weights).for_each(|(w, &v)| *w = *w * v);
+        }
+        let v1 = w.iter().fold(T::zero(), |s, &v| s + v);
+        let ddof = T::from(ddof).unwrap();
+        let fact = match aweights {
+            None => v1 - ddof,
+            Some(a) => {
+                let v2 = w.iter().zip(a).fold(T::zero(), |s, (&w, &a)| s + w * a);
+                v1 - ddof * v2 / v1
+            }
+        };
+
+        // Two passes: center each variable on its weighted mean, then
+        // accumulate the weighted products of deviations.
+        let centered = self.map_axis(axis, |x| {
+            let mean = x.iter().zip(&w).fold(T::zero(), |s, (&x, &w)| s + w * x) / v1;
+            x.iter().map(|&x| x - mean).collect::<Vec<T>>()
+        });
+        let k = centered.len();
+        let mut values = vec![T::zero(); k * k];
+        for i in 0..k {
+            for j in i..k {
+                let c = centered[i]
+                    .iter()
+                    .zip(&centered[j])
+                    .zip(&w)
+                    .fold(T::zero(), |s, ((&a, &b), &w)| s + w * a * b)
+                    / fact;
+                values[i * k + j] = c;
+                values[j * k + i] = c;
+            }
+        }
+        Mat::new(k, k, values, false)
+    }
+
+    /// Returns the Pearson correlation coefficients of the variables in
+    /// the rows (`rowvar`) or columns of `self`.
+    pub fn corrcoef(&self, rowvar: bool) -> Mat<T> {
+        let mut c = self.cov(rowvar, 0);
+        let k = c.rows;
+        let d: Vec<T> = (0..k).map(|i| c.values[i * k + i].sqrt()).collect();
+        for i in 0..k {
+            for j in 0..k {
+                let r = c.values[i * k + j] / (d[i] * d[j]);
+                if !r.is_nan() {
+                    c.values[i * k + j] = r.max(-T::one()).min(T::one());
+                } else {
+                    c.values[i * k + j] = r;
+                }
+            }
+        }
+        c
+    }
+}
@@ -1,11 +1,28 @@
-use crate::full::{get_ref, get_ref_mut, identity, ix, mat_mat, mat_vec, ones, to_string, zeros};
+use crate::full::{
+    fmt_display, fmt_grid, fmt_lower_exp, get_ref, get_ref_mut, identity, ix, mat_mat, mat_vec,
+    ones, row_col, try_mat_mat, try_mat_vec, zeros,
+};
+
+use crate::arr::Arr;
+use crate::error::{check_len, Error};
+use crate::mask::Mask;
+use crate::slice::worst_not_close;
+use crate::traits::{Float, IsNaN, Norm};
 
 use num_traits::{One, Zero};
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, LowerExp};
 use std::iter::zip;
-use std::ops::{Add, AddAssign, Index, IndexMut, Mul};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Sub};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "MatData<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )
+)]
 pub struct Mat<T> {
     pub(crate) rows: usize,
     pub(crate) cols: usize,
@@ -15,6 +32,33 @@ pub struct Mat<T> {
     pub(crate) col_major: bool,
 }
 
+// Unvalidated matrix fields, as deserialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MatData<T> {
+    rows: usize,
+    cols: usize,
+    values: Vec<T>,
+    col_major: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<MatData<T>> for Mat<T> {
+    type Error = String;
+
+    fn try_from(m: MatData<T>) -> Result<Self, Self::Error> {
+        if m.rows.checked_mul(m.cols) != Some(m.values.len()) {
+            return Err(format!(
+                "matrix has {} values, expected {}x{}",
+                m.values.len(),
+                m.rows,
+                m.cols
+            ));
+        }
+        Ok(Mat::new(m.rows, m.cols, m.values, m.col_major))
+    }
+}
+
 impl<T> Mat<T> {
     pub fn new(rows: usize, cols: usize, values: Vec<T>, col_major: bool) -> Self {
         Self {
@@ -25,6 +69,23 @@ impl<T> Mat<T> {
         }
     }
 
+    /// Returns a `Length` error if `values.len() != rows * cols`, or a
+    /// `Shape` error, comparing with `values` as a column, if `rows * cols`
+    /// overflows.
+    pub fn try_new(
+        rows: usize,
+        cols: usize,
+        values: Vec<T>,
+        col_major: bool,
+    ) -> Result<Self, Error> {
+        let len = rows.checked_mul(cols).ok_or(Error::Shape {
+            a: (rows, cols),
+            b: (values.len(), 1),
+        })?;
+        check_len(len, values.len())?;
+        Ok(Self::new(rows, cols, values, col_major))
+    }
+
     pub fn from_fn(
         rows: usize,
         cols: usize,
@@ -207,6 +268,60 @@ impl<T> Mat<T> {
         }
     }
 
+    /// Returns a row-major mask that is true where `T::is_nan(self[(r, c)])`.
+    pub fn is_nan(&self) -> Mask
+    where
+        T: IsNaN,
+    {
+        self.mask_by(|v| v.is_nan())
+    }
+
+    /// Returns a row-major mask of `f(self[(r, c)])`.
+    pub(crate) fn mask_by(&self, mut f: impl FnMut(&T) -> bool) -> Mask {
+        Mask::from_fn(self.rows * self.cols, |i| {
+            f(&self.values[ix(
+                self.rows,
+                self.cols,
+                i / self.cols,
+                i % self.cols,
+                self.col_major,
+            )])
+        })
+    }
+
+    /// Returns the elements where the row-major `mask` is true, in row-major order.
+    pub fn select_mask(&self, mask: &Mask) -> Arr<T>
+    where
+        T: Clone,
+    {
+        assert_eq!(mask.len(), self.rows * self.cols);
+        Arr {
+            values: mask
+                .indices()
+                .into_iter()
+                .map(|i| self[(i / self.cols, i % self.cols)].clone())
+                .collect(),
+        }
+    }
+
+    /// Sets the elements where the row-major `mask` is true to `v`.
+    pub fn set_where(&mut self, mask: &Mask, v: T)
+    where
+        T: Clone,
+    {
+        assert_eq!(mask.len(), self.rows * self.cols);
+        for i in mask.indices() {
+            let j = ix(
+                self.rows,
+                self.cols,
+                i / self.cols,
+                i % self.cols,
+                self.col_major,
+            );
+            self.values[j] = v.clone();
+        }
+    }
+
     // pub fn select(&self, rows: Option<&[usize]>, cols: Option<&[usize]>) -> Self {
     //     if self.col_major {
     //     } else {
@@ -226,6 +341,14 @@ impl<T> Mat<T> {
         mat_vec(self.rows, self.cols, &self.values, b, self.col_major)
     }
 
+    /// Returns a `Length` error if `b.len() != self.cols()`.
+    pub fn try_mat_vec(&self, b: &[T]) -> Result<Vec<T>, Error>
+    where
+        T: Mul<Output = T> + Add<Output = T> + Zero + Copy,
+    {
+        try_mat_vec(self.rows, self.cols, &self.values, b, self.col_major)
+    }
+
     pub fn mat_mat(&self, b: &Self) -> Self
     where
         T: Mul<Output = T> + AddAssign + Zero + Copy,
@@ -245,6 +368,62 @@ impl<T> Mat<T> {
             col_major: self.col_major,
         }
     }
+
+    /// Returns a `Shape` error if `b.rows() != self.cols()`.
+    pub fn try_mat_mat(&self, b: &Self) -> Result<Self, Error>
+    where
+        T: Mul<Output = T> + AddAssign + Zero + Copy,
+    {
+        Ok(Self {
+            rows: self.rows,
+            cols: b.cols,
+            values: try_mat_mat(
+                self.rows,
+                self.cols,
+                &self.values,
+                b.rows,
+                b.cols,
+                &b.values,
+                self.col_major,
+            )?,
+            col_major: self.col_major,
+        })
+    }
+}
+
+impl<T> Mat<T>
+where
+    T: Copy + PartialEq + Sub<Output = T>,
+{
+    /// Returns true if `self` and `other` have the same shape and all
+    /// elements satisfy `norm(a[i,j] - b[i,j]) <= atol + rtol * norm(b[i,j])`.
+    pub fn approx_eq<F>(&self, other: &Self, rtol: F, atol: F) -> bool
+    where
+        T: Norm<F>,
+        F: Float,
+    {
+        self.shape() == other.shape() && self.worst_not_close(other, rtol, atol).is_none()
+    }
+
+    /// Returns the row and column of the element that most exceeds the
+    /// tolerance `atol + rtol * norm(b[i,j])`, or `None` if all elements
+    /// are close. Panics if the shapes differ.
+    pub fn worst_not_close<F>(&self, other: &Self, rtol: F, atol: F) -> Option<(usize, usize)>
+    where
+        T: Norm<F>,
+        F: Float,
+    {
+        assert_eq!(self.shape(), other.shape());
+        let i = if self.col_major == other.col_major {
+            worst_not_close(&self.values, &other.values, rtol, atol)
+        } else {
+            let b: Vec<T> = (0..self.values.len())
+                .map(|i| other[row_col(self.rows, self.cols, i, self.col_major)])
+                .collect();
+            worst_not_close(&self.values, &b, rtol, atol)
+        }?;
+        Some(row_col(self.rows, self.cols, i, self.col_major))
+    }
 }
 
 impl<T> Index<(usize, usize)> for Mat<T> {
@@ -263,15 +442,82 @@ impl<T> IndexMut<(usize, usize)> for Mat<T> {
     }
 }
 
+impl<T> PartialEq for Mat<T>
+where
+    T: PartialEq,
+{
+    /// Matrices are equal if they have the same shape and
+    /// element values, regardless of storage order.
+    fn eq(&self, other: &Self) -> bool {
+        if self.shape() != other.shape() {
+            return false;
+        }
+        if self.col_major == other.col_major {
+            return self.values == other.values;
+        }
+        (0..self.rows).all(|r| (0..self.cols).all(|c| self[(r, c)] == other[(r, c)]))
+    }
+}
+
 impl<T> Display for Mat<T>
 where
-    T: Copy + Display,
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_grid(
+            f,
+            self.rows,
+            self.cols,
+            &self.values,
+            self.col_major,
+            fmt_display,
+        )
+    }
+}
+
+impl<T> LowerExp for Mat<T>
+where
+    T: LowerExp,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
+        fmt_grid(
             f,
-            "{}",
-            to_string(self.rows, self.cols, &self.values, self.col_major)
+            self.rows,
+            self.cols,
+            &self.values,
+            self.col_major,
+            fmt_lower_exp,
         )
     }
 }
+
+/// Asserts that two matrices have the same shape and are element-wise
+/// equal within a relative and absolute tolerance (default `1e-5` and
+/// `1e-8`), reporting the worst offending element on failure.
+#[macro_export]
+macro_rules! assert_mat_close {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::assert_mat_close!($a, $b, 1e-5, 1e-8)
+    };
+    ($a:expr, $b:expr, $rtol:expr, $atol:expr $(,)?) => {{
+        let (a, b) = (&$a, &$b);
+        assert_eq!(
+            a.shape(),
+            b.shape(),
+            "matrices differ in shape: {:?} != {:?}",
+            a.shape(),
+            b.shape()
+        );
+        if let Some((r, c)) = a.worst_not_close(b, $rtol, $atol) {
+            panic!(
+                "matrices not close at ({}, {}): {} != {} (rtol={}, atol={})",
+                r,
+                c,
+                a[(r, c)],
+                b[(r, c)],
+                $rtol,
+                $atol
+            );
+        }
+    }};
+}
@@ -1,4 +1,10 @@
-use crate::mat::Mat;
+use crate::arr::Arr;
+use crate::error::Error;
+use crate::mat::{Axis, Mat};
+use crate::slice::Spacing;
+use crate::stats::QuantileMethod;
+use crate::{assert_arr_close, assert_mat_close};
+use num_complex::Complex64;
 
 #[test]
 fn test_matrix() {
@@ -11,3 +17,200 @@ fn test_matrix() {
     a *= b;
     // println!("{}", -a - 3.0);
 }
+
+#[test]
+fn test_display() {
+    let mut a = Mat::<f64>::zeros(2, 3, true);
+    a[(0, 0)] = 1.5;
+    a[(1, 2)] = -10.25;
+
+    assert_eq!(
+        format!("{}", a),
+        "   1.5      0      0\n     0      0 -10.25"
+    );
+    assert_eq!(format!("{:.1}", a), "  1.5   0.0   0.0\n  0.0   0.0 -10.2");
+    assert_eq!(
+        format!("{:<7.1e}", a),
+        "1.5e0   0.0e0   0.0e0  \n0.0e0   0.0e0   -1.0e1 "
+    );
+
+    let c = Mat::<Complex64>::identity(2, false) * Complex64::new(1.0, -2.0);
+    assert_eq!(format!("{:.1}", c), "1.0-2.0i 0.0+0.0i\n0.0+0.0i 1.0-2.0i");
+}
+
+#[test]
+fn test_display_truncated() {
+    let a = Mat::<i32>::identity(100, false);
+    let s = a.to_string();
+    let lines: Vec<&str> = s.lines().collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[0], "  1   0   0 ...   0   0   0");
+    assert_eq!(lines[3], "...");
+    assert_eq!(lines[6], "  0   0   0 ...   0   0   1");
+
+    let b = Mat::<i32>::identity(10, false);
+    assert_eq!(b.to_string().lines().count(), 10);
+}
+
+#[test]
+fn test_approx_eq() {
+    let mut a = Mat::<f64>::zeros(2, 3, false);
+    let mut b = Mat::<f64>::zeros(2, 3, true);
+    a[(1, 2)] = 1.0;
+    b[(1, 2)] = 1.0 + 1e-7;
+    assert!(a != b);
+    assert!(a.approx_eq(&b, 1e-5, 0.0));
+    assert_eq!(a.worst_not_close(&b, 1e-9, 0.0), Some((1, 2)));
+    assert!(!a.approx_eq(&Mat::zeros(3, 2, false), 1e-5, 1e-8));
+
+    b[(1, 2)] = 1.0;
+    assert!(a == b);
+    assert_mat_close!(a, b);
+}
+
+#[test]
+#[should_panic(expected = "matrices not close at (0, 1): 0 != 0.5")]
+fn test_assert_mat_close() {
+    let a = Mat::<f64>::zeros(2, 2, true);
+    let mut b = Mat::<f64>::zeros(2, 2, false);
+    b[(0, 1)] = 0.5;
+    b[(1, 0)] = 0.1;
+    assert_mat_close!(a, b, 0.0, 0.01);
+}
+
+#[test]
+fn test_try_mat_mat() {
+    let a = Mat::<f64>::ones(2, 3, false);
+    let b = Mat::<f64>::ones(3, 4, false);
+    let c = a.try_mat_mat(&b).unwrap();
+    assert_eq!(c.shape(), (2, 4));
+    assert!(c.values().iter().all(|&v| v == 3.0));
+
+    assert_eq!(
+        b.try_mat_mat(&a).err(),
+        Some(Error::Shape {
+            a: (3, 4),
+            b: (2, 3)
+        })
+    );
+    assert_eq!(a.try_mat_vec(&[1.0, 2.0, 3.0]), Ok(vec![6.0, 6.0]));
+    assert!(a.try_mat_vec(&[1.0, 2.0]).is_err());
+    assert!(Mat::try_new(2, 2, vec![1.0; 3], true).is_err());
+    assert!(Mat::try_new(2, 2, vec![1.0; 4], true).is_ok());
+    assert!(Mat::try_new(usize::MAX, 2, vec![1.0; 2], true).is_err());
+}
+
+#[test]
+fn test_nan_axis() {
+    let nan = f64::NAN;
+    for col_major in [false, true] {
+        let mut m = Mat::<f64>::zeros(2, 3, col_major);
+        m[(0, 0)] = 1.0;
+        m[(1, 0)] = 3.0;
+        m[(0, 1)] = nan;
+        m[(1, 1)] = -2.0;
+        m[(0, 2)] = nan;
+        m[(1, 2)] = nan;
+
+        assert_eq!(m.nansum(Axis::Rows).values(), &[4.0, -2.0, 0.0]);
+        assert_eq!(m.nansum(Axis::Cols).values(), &[1.0, 1.0]);
+        let mean = m.nanmean(Axis::Rows);
+        assert_eq!(&mean[..2], &[2.0, -2.0]);
+        assert!(mean[2].is_nan());
+        assert_eq!(m.nanmax(Axis::Cols).values(), &[1.0, 3.0]);
+        assert_eq!(m.nanmin(Axis::Cols).values(), &[1.0, -2.0]);
+        assert_eq!(m.nanargmax(Axis::Rows), vec![Some(1), Some(1), None]);
+        assert_eq!(m.nanargmin(Axis::Cols), vec![Some(0), Some(1)]);
+    }
+}
+
+#[test]
+fn test_statistics_axis() {
+    for col_major in [false, true] {
+        let values = if col_major {
+            vec![1.0, 2.0, 3.0, 4.0, 2.0, 9.0]
+        } else {
+            vec![1.0, 4.0, 2.0, 2.0, 3.0, 9.0]
+        };
+        let m = Mat::new(3, 2, values, col_major);
+
+        assert_eq!(m.var(Axis::Rows, 0).values(), &[2.0 / 3.0, 26.0 / 3.0]);
+        assert_arr_close!(
+            m.std(Axis::Cols, 1),
+            Arr::with_vec(vec![4.5f64.sqrt(), 0.0, 18f64.sqrt()])
+        );
+        assert_eq!(m.median(Axis::Rows).values(), &[2.0, 4.0]);
+        let q = m.quantile(Axis::Rows, 0.25, QuantileMethod::Lower);
+        assert_eq!(q.values(), &[1.0, 2.0]);
+        assert_eq!(
+            m.percentile(Axis::Cols, 50.0, QuantileMethod::Linear)
+                .values(),
+            &[2.5, 2.0, 6.0]
+        );
+        assert_eq!(m.mode(Axis::Cols).values(), &[1.0, 2.0, 3.0]);
+        assert_eq!(m.skewness(Axis::Rows)[0], 0.0);
+    }
+}
+
+#[test]
+fn test_cov() {
+    let m = Mat::new(2, 3, vec![0.0, 1.0, 2.0, 2.0, 1.0, 0.0], false);
+    let c = m.cov(true, 1);
+    assert_mat_close!(c, Mat::new(2, 2, vec![1.0, -1.0, -1.0, 1.0], false));
+    assert_mat_close!(
+        m.cov(true, 0),
+        Mat::new(
+            2,
+            2,
+            vec![2.0 / 3.0, -2.0 / 3.0, -2.0 / 3.0, 2.0 / 3.0],
+            false
+        )
+    );
+
+    let t = Mat::new(3, 2, vec![0.0, 2.0, 1.0, 1.0, 2.0, 0.0], false);
+    assert_mat_close!(t.cov(false, 1), c);
+    assert_mat_close!(t.corrcoef(false), c);
+
+    let x = Mat::new(1, 3, vec![0.0, 1.0, 2.0], false);
+    let w = [1.0, 2.0, 1.0];
+    assert_mat_close!(
+        x.cov_weighted(true, 1, Some(&w), None),
+        Mat::new(1, 1, vec![2.0 / 3.0], false)
+    );
+    assert_mat_close!(
+        x.cov_weighted(true, 1, None, Some(&w)),
+        Mat::new(1, 1, vec![0.8], false)
+    );
+
+    let m = Mat::<f64>::new(2, 3, vec![1.0, 2.0, 4.0, 3.0, 3.0, 3.0], false);
+    let r = m.corrcoef(true);
+    assert_eq!(r[(0, 0)], 1.0);
+    assert!(r[(0, 1)].is_nan());
+}
+
+#[test]
+fn test_integrate_axis() {
+    let m = Mat::new(2, 3, vec![1.0, 2.0, 4.0, 7.0, 11.0, 16.0], false);
+    let dx = Spacing::Uniform(1.0);
+    assert_eq!(m.trapz(Axis::Rows, dx).vec(), vec![4.0, 6.5, 10.0]);
+    assert_eq!(m.trapz(Axis::Cols, dx).vec(), vec![4.5, 22.5]);
+    assert_arr_close!(
+        m.simpson(Axis::Cols, Spacing::Coords(&[0.0, 1.0, 2.0])),
+        Arr::with_vec(vec![13.0 / 3.0, 67.0 / 3.0])
+    );
+    assert!(m.cumtrapz(Axis::Rows, dx, None) == Mat::new(1, 3, vec![4.0, 6.5, 10.0], false));
+    assert!(
+        m.cumtrapz(Axis::Cols, dx, Some(0.0))
+            == Mat::new(2, 3, vec![0.0, 1.5, 4.5, 0.0, 9.0, 22.5], false)
+    );
+    let empty = Mat::<f64>::new(0, 3, vec![], false).cumtrapz(Axis::Rows, dx, Some(0.0));
+    assert_eq!((empty.rows(), empty.cols()), (0, 3));
+    assert!(empty.values().is_empty());
+    assert!(
+        m.gradient(Axis::Cols, dx, 1) == Mat::new(2, 3, vec![1.0, 1.5, 2.0, 4.0, 4.5, 5.0], false)
+    );
+    assert!(
+        m.gradient(Axis::Rows, Spacing::Uniform(0.5), 1)
+            == Mat::new(2, 3, vec![12.0, 18.0, 24.0, 12.0, 18.0, 24.0], false)
+    );
+}
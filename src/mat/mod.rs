@@ -1,9 +1,17 @@
+mod axis;
 mod cmat;
+mod cmp;
+mod cov;
 mod mat;
 mod std_ops;
+mod view;
 
 #[cfg(test)]
 mod mat_test;
+#[cfg(all(test, feature = "serde"))]
+mod serde_test;
 
+pub use axis::*;
 pub use cmat::*;
 pub use mat::*;
+pub use view::*;
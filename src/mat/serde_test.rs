@@ -0,0 +1,55 @@
+use crate::arr::Arr;
+use crate::mat::Mat;
+use num_complex::Complex64;
+
+#[test]
+fn test_serde_arr() {
+    let a = Arr::<f64>::with_vec(vec![1.0, 2.5, -3.0]);
+    let json = serde_json::to_string(&a).unwrap();
+    assert_eq!(json, "[1.0,2.5,-3.0]");
+    let b: Arr<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(b.values(), a.values());
+
+    let c = Arr::<Complex64>::with_vec(vec![Complex64::new(1.0, -1.0)]);
+    let bytes = bincode::serialize(&c).unwrap();
+    let d: Arr<Complex64> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(d.values(), c.values());
+}
+
+#[test]
+fn test_serde_mat() {
+    let mut m = Mat::<f64>::zeros(2, 3, true);
+    m[(1, 2)] = 4.0;
+    let json = serde_json::to_string(&m).unwrap();
+    assert_eq!(
+        json,
+        r#"{"rows":2,"cols":3,"values":[0.0,0.0,0.0,0.0,0.0,4.0],"col_major":true}"#
+    );
+    let n: Mat<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(n.shape(), (2, 3));
+    assert!(n.col_major());
+    assert_eq!(n[(1, 2)], 4.0);
+
+    let bytes = bincode::serialize(&m).unwrap();
+    let n: Mat<f64> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(n.values(), m.values());
+
+    let err = serde_json::from_str::<Mat<f64>>(
+        r#"{"rows":2,"cols":2,"values":[1.0,2.0,3.0],"col_major":false}"#,
+    )
+    .err()
+    .unwrap();
+    assert!(err
+        .to_string()
+        .contains("matrix has 3 values, expected 2x2"));
+
+    // The element count overflows usize on 64-bit targets.
+    let err = serde_json::from_str::<Mat<f64>>(
+        r#"{"rows":4294967296,"cols":4294967296,"values":[],"col_major":false}"#,
+    )
+    .err()
+    .unwrap();
+    assert!(err
+        .to_string()
+        .contains("matrix has 0 values, expected 4294967296x4294967296"));
+}
@@ -0,0 +1,112 @@
+use crate::full::{fmt_display, fmt_grid, fmt_lower_exp, get_ref};
+use crate::mat::Mat;
+
+use std::fmt::{Display, Formatter, LowerExp};
+use std::ops::Index;
+
+/// A matrix borrowing its element values, e.g. from a memory map.
+#[derive(Clone, Copy)]
+pub struct MatView<'a, T> {
+    pub(crate) rows: usize,
+    pub(crate) cols: usize,
+    pub(crate) values: &'a [T],
+    pub(crate) col_major: bool,
+}
+
+impl<'a, T> MatView<'a, T> {
+    pub fn new(rows: usize, cols: usize, values: &'a [T], col_major: bool) -> Self {
+        assert_eq!(values.len(), rows * cols);
+        Self {
+            rows,
+            cols,
+            values,
+            col_major,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn values(&self) -> &'a [T] {
+        self.values
+    }
+
+    pub fn col_major(&self) -> bool {
+        self.col_major
+    }
+
+    pub fn get_ref(&self, row: usize, col: usize) -> &'a T {
+        assert!(row < self.rows);
+        assert!(col < self.cols);
+        get_ref(self.rows, self.cols, self.values, row, col, self.col_major)
+    }
+
+    /// Returns an owned copy of the matrix.
+    pub fn to_mat(&self) -> Mat<T>
+    where
+        T: Clone,
+    {
+        Mat::new(self.rows, self.cols, self.values.to_vec(), self.col_major)
+    }
+}
+
+impl<'a, T> From<&'a Mat<T>> for MatView<'a, T> {
+    fn from(m: &'a Mat<T>) -> Self {
+        Self {
+            rows: m.rows,
+            cols: m.cols,
+            values: &m.values,
+            col_major: m.col_major,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        let (r, c) = index;
+        self.get_ref(r, c)
+    }
+}
+
+impl<T> Display for MatView<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_grid(
+            f,
+            self.rows,
+            self.cols,
+            self.values,
+            self.col_major,
+            fmt_display,
+        )
+    }
+}
+
+impl<T> LowerExp for MatView<'_, T>
+where
+    T: LowerExp,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_grid(
+            f,
+            self.rows,
+            self.cols,
+            self.values,
+            self.col_major,
+            fmt_lower_exp,
+        )
+    }
+}
@@ -0,0 +1,66 @@
+use crate::poly::linalg::lstsq;
+use crate::poly::Poly;
+use crate::traits::Float;
+
+/// Returns the polynomial of degree `deg` that fits the points `(x, y)`
+/// with the least squared error. The columns of the Vandermonde matrix
+/// are scaled to unit norm to improve conditioning.
+///
+/// Panics if `x` and `y` differ in length, there are no more than
+/// `deg` points, or the fit is rank deficient because there are no more
+/// than `deg` distinct values of `x`.
+pub fn polyfit<F: Float>(x: &[F], y: &[F], deg: usize) -> Poly<F> {
+    polyfit_weighted(x, y, deg, None)
+}
+
+/// Returns the polynomial of degree `deg` that minimises the sum of the
+/// squared errors at the points `(x, y)` multiplied by the weights `w`.
+/// As in NumPy, the weights apply to the unsquared residuals, so use
+/// `1 / sigma` for points with standard deviation `sigma`.
+///
+/// Panics as `polyfit` does, counting only the points with non-zero weight
+/// as distinct values of `x`.
+pub fn polyfit_weighted<F: Float>(x: &[F], y: &[F], deg: usize, w: Option<&[F]>) -> Poly<F> {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    if let Some(w) = w {
+        assert_eq!(w.len(), x.len(), "weights must have the same length as x");
+    }
+    let m = x.len();
+    let n = deg + 1;
+    assert!(
+        m >= n,
+        "polynomial degree must be less than the number of points"
+    );
+
+    let weight = |i: usize| w.map_or(F::one(), |w| w[i]);
+    let mut a = vec![F::zero(); m * n];
+    for i in 0..m {
+        let mut v = weight(i);
+        for j in 0..n {
+            a[i * n + j] = v;
+            v = v * x[i];
+        }
+    }
+    let b = (0..m).map(|i| y[i] * weight(i)).collect();
+
+    let scale: Vec<F> = (0..n)
+        .map(|j| {
+            let s = (0..m)
+                .fold(F::zero(), |s, i| s + a[i * n + j] * a[i * n + j])
+                .sqrt();
+            if s == F::zero() {
+                F::one()
+            } else {
+                s
+            }
+        })
+        .collect();
+    for i in 0..m {
+        for j in 0..n {
+            a[i * n + j] = a[i * n + j] / scale[j];
+        }
+    }
+
+    let c = lstsq(a, b, m, n);
+    Poly::new(c.into_iter().zip(scale).map(|(c, s)| c / s).collect())
+}
@@ -0,0 +1,276 @@
+use crate::traits::Float;
+
+use num_complex::Complex;
+
+/// Solves the least squares problem `min |a x - b|` for the row-major
+/// `m` by `n` matrix `a`, with `m >= n`, using Householder QR
+/// factorisation.
+///
+/// Panics if `a` is rank deficient, i.e. a diagonal element of `R` is
+/// negligible relative to the largest one.
+pub(crate) fn lstsq<F: Float>(mut a: Vec<F>, mut b: Vec<F>, m: usize, n: usize) -> Vec<F> {
+    assert!(m >= n, "least squares system is underdetermined");
+    let two = F::one() + F::one();
+    for k in 0..n {
+        let norm = (k..m)
+            .map(|i| a[i * n + k] * a[i * n + k])
+            .fold(F::zero(), |s, v| s + v)
+            .sqrt();
+        if norm == F::zero() {
+            continue;
+        }
+        let alpha = if a[k * n + k] > F::zero() {
+            -norm
+        } else {
+            norm
+        };
+        let mut v: Vec<F> = (k..m).map(|i| a[i * n + k]).collect();
+        v[0] = v[0] - alpha;
+        let vv = v.iter().fold(F::zero(), |s, &x| s + x * x);
+        if vv == F::zero() {
+            continue;
+        }
+        for j in k..n {
+            let s = two * (k..m).fold(F::zero(), |s, i| s + v[i - k] * a[i * n + j]) / vv;
+            (k..m).for_each(|i| a[i * n + j] = a[i * n + j] - s * v[i - k]);
+        }
+        let s = two * (k..m).fold(F::zero(), |s, i| s + v[i - k] * b[i]) / vv;
+        (k..m).for_each(|i| b[i] = b[i] - s * v[i - k]);
+    }
+
+    let diag = |k: usize| a[k * n + k].abs();
+    let rmax = (0..n).map(diag).fold(F::zero(), F::max);
+    let tol = rmax * F::epsilon() * F::from(m).unwrap();
+    assert!(
+        (0..n).all(|k| diag(k) > tol),
+        "least squares system is rank deficient"
+    );
+
+    let mut x = vec![F::zero(); n];
+    for k in (0..n).rev() {
+        let s = (k + 1..n).fold(b[k], |s, j| s - a[k * n + j] * x[j]);
+        x[k] = s / a[k * n + k];
+    }
+    x
+}
+
+/// Returns the eigenvalues of the `n` by `n` upper Hessenberg matrix
+/// `h`, stored row-major, using the Francis double shift QR algorithm.
+/// Eigenvalues that fail to converge are NaN.
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn hessenberg_eigenvalues<F: Float>(h: &[F], n: usize) -> Vec<Complex<F>> {
+    // 1-based indexing, following the EISPACK formulation of the algorithm.
+    let mut a = vec![vec![F::zero(); n + 1]; n + 1];
+    for i in 0..n {
+        for j in 0..n {
+            a[i + 1][j + 1] = h[i * n + j];
+        }
+    }
+    balance(&mut a, n);
+
+    let c = |v: f64| F::from(v).unwrap();
+    let sign = |a: F, b: F| if b >= F::zero() { a.abs() } else { -a.abs() };
+    let mut wr = vec![F::nan(); n + 1];
+    let mut wi = vec![F::nan(); n + 1];
+
+    let mut anorm = F::zero();
+    for i in 1..=n {
+        for j in i.saturating_sub(1).max(1)..=n {
+            anorm = anorm + a[i][j].abs();
+        }
+    }
+
+    let mut nn = n;
+    let mut t = F::zero();
+    'outer: while nn >= 1 {
+        let mut its = 0;
+        loop {
+            let mut l = nn;
+            while l >= 2 {
+                let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+                if s == F::zero() {
+                    s = anorm;
+                }
+                if a[l][l - 1].abs() + s == s {
+                    a[l][l - 1] = F::zero();
+                    break;
+                }
+                l -= 1;
+            }
+            let mut x = a[nn][nn];
+            if l == nn {
+                wr[nn] = x + t;
+                wi[nn] = F::zero();
+                nn -= 1;
+                break;
+            }
+            let mut y = a[nn - 1][nn - 1];
+            let mut w = a[nn][nn - 1] * a[nn - 1][nn];
+            if l == nn - 1 {
+                let p = c(0.5) * (y - x);
+                let q = p * p + w;
+                let mut z = q.abs().sqrt();
+                x = x + t;
+                if q >= F::zero() {
+                    z = p + sign(z, p);
+                    wr[nn - 1] = x + z;
+                    wr[nn] = x + z;
+                    if z != F::zero() {
+                        wr[nn] = x - w / z;
+                    }
+                    wi[nn - 1] = F::zero();
+                    wi[nn] = F::zero();
+                } else {
+                    wr[nn - 1] = x + p;
+                    wr[nn] = x + p;
+                    wi[nn - 1] = -z;
+                    wi[nn] = z;
+                }
+                nn -= 2;
+                break;
+            }
+            if its == 60 {
+                break 'outer;
+            }
+            if its == 10 || its == 20 {
+                // Exceptional shift.
+                t = t + x;
+                for i in 1..=nn {
+                    a[i][i] = a[i][i] - x;
+                }
+                let s = a[nn][nn - 1].abs() + a[nn - 1][nn - 2].abs();
+                x = c(0.75) * s;
+                y = x;
+                w = c(-0.4375) * s * s;
+            }
+            its += 1;
+
+            let mut m = nn - 2;
+            let (mut p, mut q, mut r, mut z);
+            loop {
+                z = a[m][m];
+                r = x - z;
+                let s = y - z;
+                p = (r * s - w) / a[m + 1][m] + a[m][m + 1];
+                q = a[m + 1][m + 1] - z - r - s;
+                r = a[m + 2][m + 1];
+                let s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+                if m == l {
+                    break;
+                }
+                let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+                if u + v == v {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in m + 2..=nn {
+                a[i][i - 2] = F::zero();
+                if i != m + 2 {
+                    a[i][i - 3] = F::zero();
+                }
+            }
+            for k in m..nn {
+                if k != m {
+                    p = a[k][k - 1];
+                    q = a[k + 1][k - 1];
+                    r = if k != nn - 1 {
+                        a[k + 2][k - 1]
+                    } else {
+                        F::zero()
+                    };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x != F::zero() {
+                        p = p / x;
+                        q = q / x;
+                        r = r / x;
+                    }
+                }
+                let s = sign((p * p + q * q + r * r).sqrt(), p);
+                if s == F::zero() {
+                    continue;
+                }
+                if k == m {
+                    if l != m {
+                        a[k][k - 1] = -a[k][k - 1];
+                    }
+                } else {
+                    a[k][k - 1] = -s * x;
+                }
+                p = p + s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q = q / p;
+                r = r / p;
+                for j in k..=nn {
+                    p = a[k][j] + q * a[k + 1][j];
+                    if k != nn - 1 {
+                        p = p + r * a[k + 2][j];
+                        a[k + 2][j] = a[k + 2][j] - p * z;
+                    }
+                    a[k + 1][j] = a[k + 1][j] - p * y;
+                    a[k][j] = a[k][j] - p * x;
+                }
+                for i in l..=nn.min(k + 3) {
+                    p = x * a[i][k] + y * a[i][k + 1];
+                    if k != nn - 1 {
+                        p = p + z * a[i][k + 2];
+                        a[i][k + 2] = a[i][k + 2] - p * r;
+                    }
+                    a[i][k + 1] = a[i][k + 1] - p * q;
+                    a[i][k] = a[i][k] - p;
+                }
+            }
+        }
+    }
+
+    (1..=n).map(|i| Complex::new(wr[i], wi[i])).collect()
+}
+
+// Scales the rows and columns of the 1-based matrix `a` by powers of two
+// so that their norms are comparable, which improves the accuracy of the
+// computed eigenvalues. The scaling is a similarity transform and keeps
+// the Hessenberg form.
+#[allow(clippy::needless_range_loop)]
+fn balance<F: Float>(a: &mut [Vec<F>], n: usize) {
+    let radix = F::from(2.0).unwrap();
+    let sqrdx = radix * radix;
+    let mut done = false;
+    while !done {
+        done = true;
+        for i in 1..=n {
+            let mut r = F::zero();
+            let mut c = F::zero();
+            for j in (1..=n).filter(|&j| j != i) {
+                c = c + a[j][i].abs();
+                r = r + a[i][j].abs();
+            }
+            if c == F::zero() || r == F::zero() {
+                continue;
+            }
+            let mut g = r / radix;
+            let mut f = F::one();
+            let s = c + r;
+            while c < g {
+                f = f * radix;
+                c = c * sqrdx;
+            }
+            g = r * radix;
+            while c > g {
+                f = f / radix;
+                c = c / sqrdx;
+            }
+            if (c + r) / f < F::from(0.95).unwrap() * s {
+                done = false;
+                let g = F::one() / f;
+                (1..=n).for_each(|j| a[i][j] = a[i][j] * g);
+                (1..=n).for_each(|j| a[j][i] = a[j][i] * f);
+            }
+        }
+    }
+}
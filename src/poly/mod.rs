@@ -0,0 +1,11 @@
+mod fit;
+mod linalg;
+mod ops;
+mod polynomial;
+mod roots;
+
+#[cfg(test)]
+mod poly_test;
+
+pub use fit::*;
+pub use polynomial::*;
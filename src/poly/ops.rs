@@ -0,0 +1,75 @@
+use crate::poly::Poly;
+
+use num_traits::Zero;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Combines the coefficients of `a` and `b`, padding the shorter with zeros.
+fn zip_coef<T>(a: &[T], b: &[T], f: impl Fn(T, T) -> T) -> Poly<T>
+where
+    T: Copy + Zero,
+{
+    Poly::new(
+        (0..a.len().max(b.len()))
+            .map(|i| {
+                let ai = a.get(i).copied().unwrap_or_else(T::zero);
+                let bi = b.get(i).copied().unwrap_or_else(T::zero);
+                f(ai, bi)
+            })
+            .collect(),
+    )
+}
+
+#[opimps::impl_ops(Add)]
+fn add<T>(self: Poly<T>, rhs: Poly<T>) -> Poly<T>
+where
+    T: Copy + Zero + Add<Output = T>,
+{
+    zip_coef(&self.coef, &rhs.coef, |a, b| a + b)
+}
+
+#[opimps::impl_ops(Sub)]
+fn sub<T>(self: Poly<T>, rhs: Poly<T>) -> Poly<T>
+where
+    T: Copy + Zero + Sub<Output = T>,
+{
+    zip_coef(&self.coef, &rhs.coef, |a, b| a - b)
+}
+
+#[opimps::impl_ops(Mul)]
+fn mul<T>(self: Poly<T>, rhs: Poly<T>) -> Poly<T>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if self.coef.is_empty() || rhs.coef.is_empty() {
+        return Poly::new(vec![]);
+    }
+    let mut coef = vec![T::zero(); self.coef.len() + rhs.coef.len() - 1];
+    for (i, &a) in self.coef.iter().enumerate() {
+        for (j, &b) in rhs.coef.iter().enumerate() {
+            coef[i + j] = coef[i + j] + a * b;
+        }
+    }
+    Poly::new(coef)
+}
+
+impl<T> Neg for Poly<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Poly::new(self.coef.values.into_iter().map(|c| -c).collect())
+    }
+}
+
+impl<T> Neg for &Poly<T>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Poly<T>;
+
+    fn neg(self) -> Self::Output {
+        Poly::new(self.coef.iter().map(|&c| -c).collect())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::arr::Arr;
+use crate::assert_arr_close;
+use crate::poly::{polyfit, polyfit_weighted, Poly};
+
+use num_complex::Complex64;
+
+// Returns the roots sorted by real then imaginary part.
+fn sorted_roots(p: &Poly<f64>) -> Vec<Complex64> {
+    let mut r = p.roots().vec();
+    r.sort_by(|a, b| (a.re, a.im).partial_cmp(&(b.re, b.im)).unwrap());
+    r
+}
+
+#[test]
+fn test_eval() {
+    let p = Poly::new(vec![1.0, -2.0, 3.0]);
+    assert_eq!(p.degree(), 2);
+    assert_eq!(p.eval(2.0), 9.0);
+    assert_eq!(p.eval_arr(&[0.0, 1.0, -1.0]).vec(), vec![1.0, 2.0, 6.0]);
+    assert_eq!(Poly::<f64>::new(vec![]).eval(3.0), 0.0);
+    assert_eq!(Poly::new(vec![1.0, 2.0, 0.0, 0.0]).trim().degree(), 1);
+}
+
+#[test]
+fn test_calculus() {
+    let p = Poly::new(vec![1.0, -2.0, 3.0, 4.0]);
+    assert_eq!(p.deriv(), Poly::new(vec![-2.0, 6.0, 12.0]));
+    assert_eq!(Poly::new(vec![5.0]).deriv(), Poly::new(vec![0.0]));
+    assert_eq!(p.integ(2.0), Poly::new(vec![2.0, 1.0, -1.0, 1.0, 1.0]));
+    assert_eq!(p.integ(0.0).deriv(), p);
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = Poly::new(vec![1.0, 2.0]);
+    let b = Poly::new(vec![-1.0, 0.0, 3.0]);
+    assert_eq!(&a + &b, Poly::new(vec![0.0, 2.0, 3.0]));
+    assert_eq!(&a - &b, Poly::new(vec![2.0, 2.0, -3.0]));
+    assert_eq!(&a * &b, Poly::new(vec![-1.0, -2.0, 3.0, 6.0]));
+    assert_eq!(-a.clone(), Poly::new(vec![-1.0, -2.0]));
+    assert_eq!((a * b).eval(2.0), 5.0 * 11.0);
+}
+
+#[test]
+fn test_polyfit() {
+    let x = Arr::linspace(-1.0, 3.0, 9, true);
+    let y = x
+        .iter()
+        .map(|&x| 0.5 - 2.0 * x + 0.25 * x * x)
+        .collect::<Vec<_>>();
+    let p = polyfit(&x, &y, 2);
+    assert_arr_close!(p.coef(), Arr::with_vec(vec![0.5, -2.0, 0.25]), 1e-12, 1e-12);
+
+    // Least squares line through points that do not lie on one.
+    let p = polyfit(&[0.0, 1.0, 2.0, 3.0], &[1.0, 3.0, 2.0, 5.0], 1);
+    assert_arr_close!(p.coef(), Arr::with_vec(vec![1.1, 1.1]), 1e-12, 1e-12);
+
+    // A zero weight removes the outlier.
+    let w = [1.0, 1.0, 1.0, 0.0];
+    let p = polyfit_weighted(&[0.0, 1.0, 2.0, 3.0], &[1.0, 3.0, 5.0, 100.0], 1, Some(&w));
+    assert_arr_close!(p.coef(), Arr::with_vec(vec![1.0, 2.0]), 1e-12, 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn test_polyfit_too_few_points() {
+    polyfit(&[0.0, 1.0], &[1.0, 2.0], 2);
+}
+
+#[test]
+#[should_panic(expected = "least squares system is rank deficient")]
+fn test_polyfit_rank_deficient() {
+    polyfit(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0], 1);
+}
+
+#[test]
+fn test_roots() {
+    // (x - 1)(x - 2)(x + 3) = x^3 - 7x + 6
+    let r = sorted_roots(&Poly::new(vec![6.0, -7.0, 0.0, 1.0]));
+    let expected = [-3.0, 1.0, 2.0];
+    assert_eq!(r.len(), 3);
+    for (r, e) in r.iter().zip(expected) {
+        assert!((r - e).norm() < 1e-12, "{} != {}", r, e);
+    }
+
+    // x^2 + 1, with a trailing zero coefficient and a root at zero.
+    let r = sorted_roots(&Poly::new(vec![0.0, 1.0, 0.0, 1.0, 0.0]));
+    let expected = [
+        Complex64::new(0.0, -1.0),
+        Complex64::new(0.0, 0.0),
+        Complex64::new(0.0, 1.0),
+    ];
+    assert_eq!(r.len(), 3);
+    for (r, e) in r.iter().zip(expected) {
+        assert!((r - e).norm() < 1e-12, "{} != {}", r, e);
+    }
+
+    assert!(Poly::new(vec![2.0]).roots().is_empty());
+    assert_eq!(
+        Poly::new(vec![2.0, 4.0]).roots().vec(),
+        vec![Complex64::new(-0.5, 0.0)]
+    );
+
+    // Roots of a high degree polynomial built from its roots.
+    let expected: Vec<f64> = (1..=10).map(|k| k as f64 * 0.5).collect();
+    let p = expected
+        .iter()
+        .fold(Poly::new(vec![1.0]), |p, &r| p * Poly::new(vec![-r, 1.0]));
+    let r = sorted_roots(&p);
+    for (r, e) in r.iter().zip(&expected) {
+        assert!((r - e).norm() < 1e-6, "{} != {}", r, e);
+    }
+    for r in p.roots().iter() {
+        let v = Poly::new(p.coef().iter().map(|&c| Complex64::new(c, 0.0)).collect()).eval(*r);
+        assert!(v.norm() < 1e-6);
+    }
+}
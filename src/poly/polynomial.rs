@@ -0,0 +1,107 @@
+use crate::arr::Arr;
+
+use num_traits::{FromPrimitive, Zero};
+use std::ops::{Add, Div, Mul};
+
+/// Polynomial `c[0] + c[1] x + ... + c[n] x^n` with its coefficients
+/// stored in increasing order of degree, as in NumPy's `Polynomial`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly<T> {
+    pub(crate) coef: Arr<T>,
+}
+
+impl<T> Poly<T> {
+    /// Creates a polynomial from coefficients in increasing order of degree.
+    pub fn new(coef: Vec<T>) -> Self {
+        Self {
+            coef: Arr { values: coef },
+        }
+    }
+
+    /// Returns the coefficients in increasing order of degree.
+    pub fn coef(&self) -> &Arr<T> {
+        &self.coef
+    }
+
+    /// Returns the degree, the number of coefficients less one.
+    pub fn degree(&self) -> usize {
+        self.coef.len().saturating_sub(1)
+    }
+
+    /// Removes trailing zero coefficients, leaving at least one.
+    pub fn trim(mut self) -> Self
+    where
+        T: Zero,
+    {
+        while self.coef.len() > 1 && self.coef.last().is_some_and(|c| c.is_zero()) {
+            self.coef.values.pop();
+        }
+        self
+    }
+}
+
+impl<T> From<Vec<T>> for Poly<T> {
+    fn from(coef: Vec<T>) -> Self {
+        Self::new(coef)
+    }
+}
+
+impl<T> From<Arr<T>> for Poly<T> {
+    fn from(coef: Arr<T>) -> Self {
+        Self { coef }
+    }
+}
+
+impl<T> Poly<T>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    /// Evaluates the polynomial at `x` using Horner's method.
+    pub fn eval(&self, x: T) -> T {
+        self.coef
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluates the polynomial at each of the values in `x`.
+    pub fn eval_arr(&self, x: &[T]) -> Arr<T> {
+        Arr {
+            values: x.iter().map(|&x| self.eval(x)).collect(),
+        }
+    }
+
+    /// Returns the derivative of the polynomial.
+    pub fn deriv(&self) -> Self
+    where
+        T: FromPrimitive,
+    {
+        if self.coef.len() <= 1 {
+            return Self::new(vec![T::zero()]);
+        }
+        Self::new(
+            self.coef
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * T::from_usize(i).unwrap())
+                .collect(),
+        )
+    }
+
+    /// Returns the integral of the polynomial with integration constant `k`.
+    pub fn integ(&self, k: T) -> Self
+    where
+        T: Div<Output = T> + FromPrimitive,
+    {
+        let mut coef = Vec::with_capacity(self.coef.len() + 1);
+        coef.push(k);
+        coef.extend(
+            self.coef
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| c / T::from_usize(i + 1).unwrap()),
+        );
+        Self::new(coef)
+    }
+}
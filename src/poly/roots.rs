@@ -0,0 +1,44 @@
+use crate::arr::Arr;
+use crate::poly::linalg::hessenberg_eigenvalues;
+use crate::poly::Poly;
+use crate::traits::Float;
+
+use num_complex::Complex;
+
+impl<F> Poly<F>
+where
+    F: Float,
+{
+    /// Returns the roots of the polynomial, with multiplicity, computed
+    /// as the eigenvalues of the companion matrix. Zero coefficients of
+    /// the highest degree terms are ignored, and zero coefficients of the
+    /// lowest give roots at exactly zero.
+    pub fn roots(&self) -> Arr<Complex<F>> {
+        let c = &self.coef;
+        let Some(hi) = c.iter().rposition(|v| !v.is_zero()) else {
+            return Arr { values: vec![] };
+        };
+        let lo = c.iter().position(|v| !v.is_zero()).unwrap();
+        let c = &c[lo..=hi];
+        let n = c.len() - 1;
+
+        let mut roots = match n {
+            0 => vec![],
+            1 => vec![Complex::new(-c[0] / c[1], F::zero())],
+            _ => {
+                // Companion matrix in upper Hessenberg form, with the
+                // negated monic coefficients along the first row.
+                let mut h = vec![F::zero(); n * n];
+                for j in 0..n {
+                    h[j] = -c[n - 1 - j] / c[n];
+                }
+                for i in 1..n {
+                    h[i * n + i - 1] = F::one();
+                }
+                hessenberg_eigenvalues(&h, n)
+            }
+        };
+        roots.extend(std::iter::repeat_n(Complex::new(F::zero(), F::zero()), lo));
+        Arr { values: roots }
+    }
+}
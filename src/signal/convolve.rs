@@ -0,0 +1,202 @@
+use crate::arr::Arr;
+use crate::fft::FftPlanner;
+use crate::mat::Mat;
+use crate::traits::{Float, Sample};
+
+use num_complex::Complex;
+
+/// Size of the result of a convolution or correlation of
+/// inputs of lengths `n >= m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConvMode {
+    /// All `n + m - 1` points of overlap.
+    #[default]
+    Full,
+    /// `n` points, centred on the full result.
+    Same,
+    /// The `n - m + 1` points where the inputs overlap completely.
+    Valid,
+}
+
+impl ConvMode {
+    // Returns the start and length of the result within the full
+    // convolution of inputs of lengths `n` and `m`.
+    fn range(self, n: usize, m: usize) -> (usize, usize) {
+        let (long, short) = (n.max(m), n.min(m));
+        match self {
+            ConvMode::Full => (0, n + m - 1),
+            ConvMode::Same => ((short - 1) / 2, long),
+            ConvMode::Valid => (short - 1, long - short + 1),
+        }
+    }
+}
+
+// Convolutions where the shorter input is at least this long use the FFT.
+const FFT_THRESHOLD: usize = 64;
+
+/// Returns the discrete linear convolution of `a` and `v`, computed
+/// directly or via the FFT for long inputs.
+pub fn convolve<F, T>(a: &[T], v: &[T], mode: ConvMode) -> Vec<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    assert!(!a.is_empty() && !v.is_empty(), "inputs must not be empty");
+    let full = if a.len().min(v.len()) >= FFT_THRESHOLD {
+        convolve_fft(a, v)
+    } else {
+        convolve_direct(a, v)
+    };
+    let (start, len) = mode.range(a.len(), v.len());
+    full[start..start + len].to_vec()
+}
+
+/// Returns the cross-correlation `c[k] = sum(a[n + k] * conj(v[n]))`.
+pub fn correlate<F, T>(a: &[T], v: &[T], mode: ConvMode) -> Vec<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let v: Vec<T> = v.iter().rev().map(|&x| x.conj()).collect();
+    convolve(a, &v, mode)
+}
+
+fn convolve_direct<F, T>(a: &[T], v: &[T]) -> Vec<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let mut out = vec![T::zero(); a.len() + v.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in v.iter().enumerate() {
+            out[i + j] = out[i + j] + x * y;
+        }
+    }
+    out
+}
+
+fn convolve_fft<F, T>(a: &[T], v: &[T]) -> Vec<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let len = a.len() + v.len() - 1;
+    let n = len.next_power_of_two();
+    let plan = FftPlanner::new().plan(n);
+    let pad = |x: &[T]| {
+        let mut buf = vec![Complex::new(F::zero(), F::zero()); n];
+        buf.iter_mut()
+            .zip(x)
+            .for_each(|(b, &x)| *b = x.to_complex());
+        plan.forward(&mut buf);
+        buf
+    };
+    let mut fa = pad(a);
+    let fv = pad(v);
+    fa.iter_mut().zip(&fv).for_each(|(x, &y)| *x = *x * y);
+    plan.inverse(&mut fa);
+    let scale = F::from(n).unwrap().recip();
+    fa[..len]
+        .iter()
+        .map(|&c| T::from_complex(c.scale(scale)))
+        .collect()
+}
+
+impl<T> Arr<T> {
+    /// Returns the discrete linear convolution with `v`.
+    pub fn convolve<F>(&self, v: &[T], mode: ConvMode) -> Arr<T>
+    where
+        F: Float,
+        T: Sample<F>,
+    {
+        Arr {
+            values: convolve(&self.values, v, mode),
+        }
+    }
+
+    /// Returns the cross-correlation with `v`.
+    pub fn correlate<F>(&self, v: &[T], mode: ConvMode) -> Arr<T>
+    where
+        F: Float,
+        T: Sample<F>,
+    {
+        Arr {
+            values: correlate(&self.values, v, mode),
+        }
+    }
+}
+
+impl<T> Mat<T> {
+    /// Returns the 2-D convolution with `kernel` as a row-major matrix.
+    /// For `Same` the result is the size of `self`, and for `Valid`
+    /// `self` must be at least as large as `kernel` in both dimensions.
+    /// Panics if either matrix is empty.
+    pub fn convolve2d<F>(&self, kernel: &Mat<T>, mode: ConvMode) -> Mat<T>
+    where
+        F: Float,
+        T: Sample<F>,
+    {
+        assert!(
+            self.rows * self.cols > 0 && kernel.rows * kernel.cols > 0,
+            "inputs must not be empty"
+        );
+        let (rows, cols) = (self.rows + kernel.rows - 1, self.cols + kernel.cols - 1);
+        let mut full = vec![T::zero(); rows * cols];
+        for p in 0..self.rows {
+            for q in 0..self.cols {
+                let x = self[(p, q)];
+                for s in 0..kernel.rows {
+                    for t in 0..kernel.cols {
+                        let o = &mut full[(p + s) * cols + q + t];
+                        *o = *o + x * kernel[(s, t)];
+                    }
+                }
+            }
+        }
+
+        let ((r0, nr), (c0, nc)) = match mode {
+            ConvMode::Full => return Mat::new(rows, cols, full, false),
+            ConvMode::Same => (
+                ((kernel.rows - 1) / 2, self.rows),
+                ((kernel.cols - 1) / 2, self.cols),
+            ),
+            ConvMode::Valid => {
+                assert!(
+                    self.rows >= kernel.rows && self.cols >= kernel.cols,
+                    "kernel must not be larger than the matrix for valid mode"
+                );
+                (
+                    (kernel.rows - 1, self.rows - kernel.rows + 1),
+                    (kernel.cols - 1, self.cols - kernel.cols + 1),
+                )
+            }
+        };
+        let values = (r0..r0 + nr)
+            .flat_map(|r| full[r * cols + c0..r * cols + c0 + nc].to_vec())
+            .collect();
+        Mat::new(nr, nc, values, false)
+    }
+
+    /// Returns the 2-D cross-correlation with `kernel`.
+    pub fn correlate2d<F>(&self, kernel: &Mat<T>, mode: ConvMode) -> Mat<T>
+    where
+        F: Float,
+        T: Sample<F>,
+    {
+        let flipped = Mat::new(
+            kernel.rows,
+            kernel.cols,
+            (0..kernel.rows * kernel.cols)
+                .map(|i| {
+                    kernel[(
+                        kernel.rows - 1 - i / kernel.cols,
+                        kernel.cols - 1 - i % kernel.cols,
+                    )]
+                        .conj()
+                })
+                .collect(),
+            false,
+        );
+        self.convolve2d(&flipped, mode)
+    }
+}
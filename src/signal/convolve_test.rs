@@ -0,0 +1,114 @@
+use crate::arr::Arr;
+use crate::mat::Mat;
+use crate::signal::{convolve, correlate, ConvMode};
+
+use num_complex::Complex64;
+use rand::Rng;
+
+#[test]
+fn test_convolve() {
+    let a = Arr::with_vec(vec![1.0, 2.0, 3.0]);
+    let v = [0.0, 1.0, 0.5];
+    assert_eq!(
+        a.convolve(&v, ConvMode::Full).vec(),
+        vec![0.0, 1.0, 2.5, 4.0, 1.5]
+    );
+    assert_eq!(a.convolve(&v, ConvMode::Same).vec(), vec![1.0, 2.5, 4.0]);
+    assert_eq!(a.convolve(&v, ConvMode::Valid).vec(), vec![2.5]);
+
+    let b = [1.0, 2.0, 3.0, 4.0, 5.0];
+    assert_eq!(
+        convolve(&b, &[1.0, 1.0], ConvMode::Same),
+        vec![1.0, 3.0, 5.0, 7.0, 9.0]
+    );
+    assert_eq!(
+        convolve(&[1.0, 1.0], &b, ConvMode::Valid),
+        vec![3.0, 5.0, 7.0, 9.0]
+    );
+
+    assert_eq!(
+        a.correlate(&v, ConvMode::Full).vec(),
+        vec![0.5, 2.0, 3.5, 3.0, 0.0]
+    );
+    assert_eq!(a.correlate(&v, ConvMode::Valid).vec(), vec![3.5]);
+
+    let c = [Complex64::new(1.0, 1.0), Complex64::new(0.0, 2.0)];
+    let d = [Complex64::new(0.0, 1.0)];
+    let expected = vec![Complex64::new(1.0, -1.0), Complex64::new(2.0, 0.0)];
+    assert_eq!(correlate(&c, &d, ConvMode::Full), expected);
+}
+
+#[test]
+fn test_convolve_fft() {
+    let mut rng = rand::thread_rng();
+    let a: Vec<f64> = (0..300).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let v: Vec<f64> = (0..100).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+    // The FFT path is taken for inputs of at least 64 values.
+    for mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid] {
+        let fast = convolve(&a, &v, mode);
+        let mut direct = vec![0.0; a.len() + v.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in v.iter().enumerate() {
+                direct[i + j] += x * y;
+            }
+        }
+        let (start, len) = match mode {
+            ConvMode::Full => (0, 399),
+            ConvMode::Same => (49, 300),
+            ConvMode::Valid => (99, 201),
+        };
+        assert_eq!(fast.len(), len);
+        for (k, y) in fast.iter().enumerate() {
+            assert!((y - direct[start + k]).abs() < 1e-10);
+        }
+    }
+
+    let c: Vec<Complex64> = a
+        .iter()
+        .zip(a.iter().rev())
+        .map(|(&x, &y)| Complex64::new(x, y))
+        .collect();
+    let fast = correlate(&c, &c[..80], ConvMode::Valid);
+    for (k, y) in fast.iter().enumerate() {
+        let direct: Complex64 = (0..80).map(|n| c[n + k] * c[n].conj()).sum();
+        assert!((y - direct).norm() < 1e-10);
+    }
+}
+
+#[test]
+fn test_convolve2d() {
+    let m = Mat::new(
+        3,
+        3,
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+        false,
+    );
+    let k = Mat::new(2, 2, vec![1.0, 0.0, 0.0, -1.0], true);
+
+    let full = m.convolve2d(&k, ConvMode::Full);
+    assert_eq!((full.rows(), full.cols()), (4, 4));
+    let expected = [
+        1.0, 2.0, 3.0, 0.0, 4.0, 4.0, 4.0, -3.0, 7.0, 4.0, 4.0, -6.0, 0.0, -7.0, -8.0, -9.0,
+    ];
+    assert_eq!(full.values(), &expected);
+
+    let same = m.convolve2d(&k, ConvMode::Same);
+    assert_eq!(
+        same.values(),
+        &[1.0, 2.0, 3.0, 4.0, 4.0, 4.0, 7.0, 4.0, 4.0]
+    );
+    let valid = m.convolve2d(&k, ConvMode::Valid);
+    assert_eq!(valid.values(), &[4.0, 4.0, 4.0, 4.0]);
+
+    let corr = m.correlate2d(&k, ConvMode::Valid);
+    assert_eq!(corr.values(), &[-4.0, -4.0, -4.0, -4.0]);
+}
+
+#[test]
+#[should_panic(expected = "inputs must not be empty")]
+fn test_convolve2d_empty() {
+    let m = Mat::new(2, 2, vec![1.0, 2.0, 3.0, 4.0], false);
+    let k = Mat::<f64>::new(0, 3, vec![], false);
+    m.correlate2d(&k, ConvMode::Full);
+}
@@ -0,0 +1,494 @@
+use crate::arr::Arr;
+use crate::signal::Window;
+use crate::traits::{Float, Sample};
+
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+/// Filter pass band, with frequencies normalised to the Nyquist
+/// frequency so that `1` is half the sampling rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterBand<F> {
+    Lowpass(F),
+    Highpass(F),
+    Bandpass(F, F),
+    Bandstop(F, F),
+}
+
+impl<F: Float> FilterBand<F> {
+    fn edges(&self) -> Vec<f64> {
+        let f = |v: F| {
+            let v = v.to_f64().unwrap();
+            assert!(v > 0.0 && v < 1.0, "frequencies must be in (0, 1)");
+            v
+        };
+        match *self {
+            FilterBand::Lowpass(w) | FilterBand::Highpass(w) => vec![f(w)],
+            FilterBand::Bandpass(lo, hi) | FilterBand::Bandstop(lo, hi) => {
+                assert!(lo < hi, "band edges must increase");
+                vec![f(lo), f(hi)]
+            }
+        }
+    }
+}
+
+/// Filters `x` with the rational transfer function `b(z) / a(z)` using
+/// the transposed direct form II structure, returning the output and the
+/// final filter state. The initial state `zi` has length
+/// `max(a.len(), b.len()) - 1` and defaults to zeros.
+pub fn lfilter<F, T>(b: &[F], a: &[F], x: &[T], zi: Option<&[T]>) -> (Arr<T>, Arr<T>)
+where
+    F: Float,
+    T: Sample<F>,
+{
+    assert!(
+        !a.is_empty() && !b.is_empty(),
+        "coefficients must not be empty"
+    );
+    assert!(a[0] != F::zero(), "a[0] must be nonzero");
+    let n = a.len().max(b.len());
+    let coef = |c: &[F]| -> Vec<F> {
+        (0..n)
+            .map(|i| c.get(i).map_or(F::zero(), |&v| v / a[0]))
+            .collect()
+    };
+    let (b, a) = (coef(b), coef(a));
+
+    let mut z = match zi {
+        Some(zi) => {
+            assert_eq!(
+                zi.len(),
+                n - 1,
+                "initial state must have length max(len(a), len(b)) - 1"
+            );
+            zi.to_vec()
+        }
+        None => vec![T::zero(); n - 1],
+    };
+    let mut y = Vec::with_capacity(x.len());
+    for &xi in x {
+        let yi = z.first().map_or(xi.scale(b[0]), |&z0| xi.scale(b[0]) + z0);
+        for j in 0..n - 1 {
+            let next = if j + 2 < n { z[j + 1] } else { T::zero() };
+            z[j] = xi.scale(b[j + 1]) - yi.scale(a[j + 1]) + next;
+        }
+        y.push(yi);
+    }
+    (Arr { values: y }, Arr { values: z })
+}
+
+/// Returns the initial state for `lfilter` corresponding to the steady
+/// state of the step response, to be scaled by the first input value.
+pub fn lfilter_zi<F>(b: &[F], a: &[F]) -> Arr<F>
+where
+    F: Float,
+{
+    assert!(!a.is_empty() && a[0] != F::zero(), "a[0] must be nonzero");
+    let n = a.len().max(b.len());
+    let coef = |c: &[F]| -> Vec<F> {
+        (0..n)
+            .map(|i| c.get(i).map_or(F::zero(), |&v| v / a[0]))
+            .collect()
+    };
+    let (b, a) = (coef(b), coef(a));
+
+    // Solves (I - A^T) zi = b[1..] - a[1..] * b[0] for the companion
+    // matrix A of `a`, whose structure gives the solution directly.
+    let mut zi = vec![F::zero(); n - 1];
+    if n > 1 {
+        let asum = a.iter().fold(F::zero(), |s, &v| s + v);
+        let bsum = (1..n).fold(F::zero(), |s, k| s + b[k] - a[k] * b[0]);
+        zi[0] = bsum / asum;
+        let (mut asum, mut csum) = (F::one(), F::zero());
+        for k in 1..n - 1 {
+            asum = asum + a[k];
+            csum = csum + b[k] - a[k] * b[0];
+            zi[k] = asum * zi[0] - csum;
+        }
+    }
+    Arr { values: zi }
+}
+
+/// Applies the filter `b(z) / a(z)` forwards and backwards, giving zero
+/// phase distortion. The signal is extended by odd reflection of
+/// `3 * max(a.len(), b.len())` values at each end, and the filter state
+/// initialised to the steady state of the edge values.
+pub fn filtfilt<F, T>(b: &[F], a: &[F], x: &[T]) -> Arr<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let zi = lfilter_zi(b, a);
+    let pad = 3 * a.len().max(b.len());
+    forward_backward(x, pad, |x: &[T]| {
+        let z: Vec<T> = zi.iter().map(|&z| x[0].scale(z)).collect();
+        lfilter(b, a, x, Some(&z)).0.values
+    })
+}
+
+/// Filters `x` with a cascade of second-order sections, each row of
+/// `sos` being `[b0, b1, b2, a0, a1, a2]`, returning the output and
+/// final state of each section.
+pub fn sosfilt<F, T>(sos: &[[F; 6]], x: &[T], zi: Option<&[[T; 2]]>) -> (Arr<T>, Vec<[T; 2]>)
+where
+    F: Float,
+    T: Sample<F>,
+{
+    if let Some(zi) = zi {
+        assert_eq!(
+            zi.len(),
+            sos.len(),
+            "initial state must have one row per section"
+        );
+    }
+    let mut y = x.to_vec();
+    let mut zf = Vec::with_capacity(sos.len());
+    for (i, s) in sos.iter().enumerate() {
+        let z = zi.map(|zi| zi[i]);
+        let (out, z) = lfilter(&s[..3], &s[3..], &y, z.as_ref().map(|z| &z[..]));
+        y = out.values;
+        zf.push([z[0], z[1]]);
+    }
+    (Arr { values: y }, zf)
+}
+
+/// Returns the steady-state initial conditions for `sosfilt`.
+pub fn sosfilt_zi<F>(sos: &[[F; 6]]) -> Vec<[F; 2]>
+where
+    F: Float,
+{
+    let mut scale = F::one();
+    sos.iter()
+        .map(|s| {
+            let zi = lfilter_zi(&s[..3], &s[3..]);
+            let z = [zi[0] * scale, zi[1] * scale];
+            scale = scale * (s[0] + s[1] + s[2]) / (s[3] + s[4] + s[5]);
+            z
+        })
+        .collect()
+}
+
+/// Applies a cascade of second-order sections forwards and backwards.
+pub fn sosfiltfilt<F, T>(sos: &[[F; 6]], x: &[T]) -> Arr<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let zi = sosfilt_zi(sos);
+    let trailing = |k: usize| sos.iter().filter(|s| s[k] == F::zero()).count();
+    let pad = 3 * (2 * sos.len() + 1 - trailing(2).min(trailing(5)));
+    forward_backward(x, pad, |x: &[T]| {
+        let z: Vec<[T; 2]> = zi
+            .iter()
+            .map(|z| [x[0].scale(z[0]), x[0].scale(z[1])])
+            .collect();
+        sosfilt(sos, x, Some(&z)).0.values
+    })
+}
+
+// Applies `filter` to the odd extension of `x`, then to the reversed
+// output, returning the central part in the original order.
+fn forward_backward<F, T>(x: &[T], pad: usize, filter: impl Fn(&[T]) -> Vec<T>) -> Arr<T>
+where
+    F: Float,
+    T: Sample<F>,
+{
+    let n = x.len();
+    assert!(n > pad, "input must be longer than the padding of {}", pad);
+    let two = F::one() + F::one();
+    let mut ext = Vec::with_capacity(n + 2 * pad);
+    ext.extend((1..=pad).rev().map(|i| x[0].scale(two) - x[i]));
+    ext.extend_from_slice(x);
+    ext.extend((1..=pad).map(|i| x[n - 1].scale(two) - x[n - 1 - i]));
+
+    let mut y = filter(&ext);
+    y.reverse();
+    let mut y = filter(&y);
+    y.reverse();
+    Arr {
+        values: y[pad..pad + n].to_vec(),
+    }
+}
+
+/// Designs a linear-phase FIR filter with `numtaps` coefficients by the
+/// window method, using a Hamming window. The gain is normalised to one
+/// at the centre of the first pass band. High-pass and band-stop filters
+/// require an odd number of taps.
+pub fn firwin<F>(numtaps: usize, band: FilterBand<F>) -> Arr<F>
+where
+    F: Float,
+{
+    firwin_with(numtaps, band, Window::Hamming)
+}
+
+/// Designs a linear-phase FIR filter like [`firwin`], using `window`
+/// in place of the Hamming window.
+pub fn firwin_with<F>(numtaps: usize, band: FilterBand<F>, window: Window<F>) -> Arr<F>
+where
+    F: Float,
+{
+    assert!(numtaps > 0, "numtaps must be positive");
+    let edges = band.edges();
+    let (pass_zero, pass_nyquist) = match band {
+        FilterBand::Lowpass(_) => (true, false),
+        FilterBand::Highpass(_) => (false, true),
+        FilterBand::Bandpass(..) => (false, false),
+        FilterBand::Bandstop(..) => (true, true),
+    };
+    assert!(
+        !pass_nyquist || numtaps % 2 == 1,
+        "a filter passing the Nyquist frequency requires an odd number of taps"
+    );
+
+    // Pairs of band edges of the pass bands.
+    let mut bands = Vec::with_capacity(edges.len() + 2);
+    if pass_zero {
+        bands.push(0.0);
+    }
+    bands.extend(&edges);
+    if pass_nyquist {
+        bands.push(1.0);
+    }
+
+    let alpha = (numtaps - 1) as f64 / 2.0;
+    let sinc = |x: f64| {
+        if x == 0.0 {
+            1.0
+        } else {
+            (PI * x).sin() / (PI * x)
+        }
+    };
+    let window = window.symmetric(numtaps);
+    let mut h: Vec<f64> = (0..numtaps)
+        .map(|i| {
+            let m = i as f64 - alpha;
+            let ideal: f64 = bands
+                .chunks(2)
+                .map(|b| b[1] * sinc(b[1] * m) - b[0] * sinc(b[0] * m))
+                .sum();
+            ideal * window[i].to_f64().unwrap()
+        })
+        .collect();
+
+    let (left, right) = (bands[0], bands[1]);
+    let f = if left == 0.0 {
+        0.0
+    } else if right == 1.0 {
+        1.0
+    } else {
+        (left + right) / 2.0
+    };
+    let gain: f64 = h
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| v * (PI * (i as f64 - alpha) * f).cos())
+        .sum();
+    h.iter_mut().for_each(|v| *v /= gain);
+    Arr {
+        values: h.into_iter().map(|v| F::from(v).unwrap()).collect(),
+    }
+}
+
+/// Designs an `order` Butterworth digital filter, returning the
+/// numerator and denominator coefficients `(b, a)`. Band-pass and
+/// band-stop filters have order `2 * order`.
+pub fn butter<F>(order: usize, band: FilterBand<F>) -> (Arr<F>, Arr<F>)
+where
+    F: Float,
+{
+    let (z, p, k) = butter_zpk(order, band);
+    let real = |c: Vec<Complex<f64>>, k: f64| Arr {
+        values: c.iter().map(|v| F::from(v.re * k).unwrap()).collect(),
+    };
+    (real(poly(&z), k), real(poly(&p), 1.0))
+}
+
+/// Designs an `order` Butterworth digital filter as second-order
+/// sections, which are numerically robust for high orders. Poles are
+/// paired with their nearest zeros and the sections ordered as by
+/// SciPy's `zpk2sos`, with the poles closest to the unit circle last.
+pub fn butter_sos<F>(order: usize, band: FilterBand<F>) -> Vec<[F; 6]>
+where
+    F: Float,
+{
+    let (z, p, k) = butter_zpk(order, band);
+    zpk2sos(z, p, k)
+        .into_iter()
+        .map(|s| s.map(|v| F::from(v).unwrap()))
+        .collect()
+}
+
+// Returns the zeros, poles and gain of the digital Butterworth filter.
+fn butter_zpk<F: Float>(
+    order: usize,
+    band: FilterBand<F>,
+) -> (Vec<Complex<f64>>, Vec<Complex<f64>>, f64) {
+    assert!(order > 0, "order must be positive");
+    let n = order as f64;
+
+    // Analog low-pass prototype with unit cutoff.
+    let p: Vec<Complex<f64>> = (0..order)
+        .map(|k| {
+            let m = 2.0 * k as f64 - n + 1.0;
+            -Complex::from_polar(1.0, PI * m / (2.0 * n))
+        })
+        .collect();
+    let z: Vec<Complex<f64>> = Vec::new();
+
+    // Pre-warp for the bilinear transform with a sample rate of 2.
+    let fs2 = 4.0;
+    let warp = |w: f64| fs2 * (PI * w / 2.0).tan();
+    let edges: Vec<f64> = band.edges().into_iter().map(warp).collect();
+
+    let one = Complex::new(1.0, 0.0);
+    let prod = |v: &[Complex<f64>]| v.iter().fold(one, |s, &x| s * x);
+    let (z, p, k) = match band {
+        FilterBand::Lowpass(_) => {
+            let wo = edges[0];
+            let scale = |v: &[Complex<f64>]| v.iter().map(|&x| x * wo).collect::<Vec<_>>();
+            (scale(&z), scale(&p), wo.powi(order as i32))
+        }
+        FilterBand::Highpass(_) => {
+            let wo = edges[0];
+            let k = (prod(&z.iter().map(|&x| -x).collect::<Vec<_>>())
+                / prod(&p.iter().map(|&x| -x).collect::<Vec<_>>()))
+            .re;
+            let invert = |v: &[Complex<f64>]| v.iter().map(|&x| wo / x).collect::<Vec<_>>();
+            let mut zh = invert(&z);
+            zh.resize(p.len(), Complex::new(0.0, 0.0));
+            (zh, invert(&p), k)
+        }
+        FilterBand::Bandpass(..) => {
+            let (wo, bw) = ((edges[0] * edges[1]).sqrt(), edges[1] - edges[0]);
+            let transform = |v: &[Complex<f64>]| {
+                let lp: Vec<Complex<f64>> = v.iter().map(|&x| x * bw / 2.0).collect();
+                let root = |x: Complex<f64>| (x * x - wo * wo).sqrt();
+                let mut out: Vec<Complex<f64>> = lp.iter().map(|&x| x + root(x)).collect();
+                out.extend(lp.iter().map(|&x| x - root(x)));
+                out
+            };
+            let mut zb = transform(&z);
+            zb.resize(zb.len() + order, Complex::new(0.0, 0.0));
+            (zb, transform(&p), bw.powi(order as i32))
+        }
+        FilterBand::Bandstop(..) => {
+            let (wo, bw) = ((edges[0] * edges[1]).sqrt(), edges[1] - edges[0]);
+            let k = (prod(&z.iter().map(|&x| -x).collect::<Vec<_>>())
+                / prod(&p.iter().map(|&x| -x).collect::<Vec<_>>()))
+            .re;
+            let transform = |v: &[Complex<f64>]| {
+                let hp: Vec<Complex<f64>> = v.iter().map(|&x| (bw / 2.0) / x).collect();
+                let root = |x: Complex<f64>| (x * x - wo * wo).sqrt();
+                let mut out: Vec<Complex<f64>> = hp.iter().map(|&x| x + root(x)).collect();
+                out.extend(hp.iter().map(|&x| x - root(x)));
+                out
+            };
+            let mut zs = transform(&z);
+            zs.extend((0..order).map(|_| Complex::new(0.0, wo)));
+            zs.extend((0..order).map(|_| Complex::new(0.0, -wo)));
+            (zs, transform(&p), k)
+        }
+    };
+
+    // Bilinear transform, mapping zeros at infinity to -1.
+    let bilinear =
+        |v: &[Complex<f64>]| v.iter().map(|&x| (fs2 + x) / (fs2 - x)).collect::<Vec<_>>();
+    let fs2_minus = |v: &[Complex<f64>]| prod(&v.iter().map(|&x| fs2 - x).collect::<Vec<_>>());
+    let k = k * (fs2_minus(&z) / fs2_minus(&p)).re;
+    let mut zd = bilinear(&z);
+    zd.resize(p.len(), Complex::new(-1.0, 0.0));
+    (zd, bilinear(&p), k)
+}
+
+// Returns the coefficients, highest power first, of the monic
+// polynomial with the given roots.
+fn poly(roots: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let mut c = vec![Complex::new(1.0, 0.0)];
+    for &r in roots {
+        c.push(Complex::new(0.0, 0.0));
+        for i in (1..c.len()).rev() {
+            c[i] = c[i] - r * c[i - 1];
+        }
+    }
+    c
+}
+
+// Returns the second-order sections of the digital filter with zeros
+// `z`, poles `p` and gain `k`, following SciPy's `zpk2sos` with nearest
+// pairing. Starting with the poles closest to the unit circle, each pole
+// pair is matched with the nearest zeros. The sections are returned in
+// the reverse order, so those poles are applied last, with the gain in
+// the first section.
+fn zpk2sos(mut z: Vec<Complex<f64>>, mut p: Vec<Complex<f64>>, k: f64) -> Vec<[f64; 6]> {
+    assert_eq!(z.len(), p.len());
+    if p.len() % 2 == 1 {
+        z.push(Complex::new(0.0, 0.0));
+        p.push(Complex::new(0.0, 0.0));
+    }
+    let (mut z, mut p) = (cplxreal(z), cplxreal(p));
+    let is_real = |r: Complex<f64>| r.im == 0.0;
+    // Removes the root of `v` nearest `x`, only considering real roots
+    // if `real`.
+    let take_nearest = |v: &mut Vec<Complex<f64>>, x: Complex<f64>, real: bool| {
+        let i = (0..v.len())
+            .filter(|&i| !real || is_real(v[i]))
+            .min_by(|&i, &j| (v[i] - x).norm().partial_cmp(&(v[j] - x).norm()).unwrap())
+            .expect("unpaired root");
+        v.remove(i)
+    };
+
+    let mut sections = Vec::with_capacity(p.len());
+    while !p.is_empty() {
+        let dist = |r: &Complex<f64>| (1.0 - r.norm()).abs();
+        let i = (0..p.len())
+            .min_by(|&i, &j| dist(&p[i]).partial_cmp(&dist(&p[j])).unwrap())
+            .unwrap();
+        let p1 = p.remove(i);
+        let lone_real = z.iter().filter(|&&r| is_real(r)).count() == 1;
+        let z1 = take_nearest(&mut z, p1, !is_real(p1) && lone_real);
+        let (z2, p2) = match (is_real(p1), is_real(z1)) {
+            (false, false) => (z1.conj(), p1.conj()),
+            (false, true) => (take_nearest(&mut z, p1, true), p1.conj()),
+            (true, false) => (z1.conj(), take_nearest(&mut p, z1, true)),
+            (true, true) => {
+                let p2 = take_nearest(&mut p, p1, true);
+                (take_nearest(&mut z, p2, true), p2)
+            }
+        };
+        sections.push(([z1, z2], [p1, p2]));
+    }
+
+    sections
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, (z, p))| {
+            let g = if i == 0 { k } else { 1.0 };
+            let (b, a) = (poly(z), poly(p));
+            [
+                b[0].re * g,
+                b[1].re * g,
+                b[2].re * g,
+                a[0].re,
+                a[1].re,
+                a[2].re,
+            ]
+        })
+        .collect()
+}
+
+// Returns the real roots, made exactly real, and one root of each
+// complex conjugate pair, that with positive imaginary part.
+fn cplxreal(roots: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+    let tol = 1e-10;
+    roots
+        .into_iter()
+        .filter_map(|r| {
+            if r.im.abs() <= tol * r.norm().max(1.0) {
+                Some(Complex::new(r.re, 0.0))
+            } else {
+                (r.im > 0.0).then_some(r)
+            }
+        })
+        .collect()
+}
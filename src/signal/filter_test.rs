@@ -0,0 +1,191 @@
+use crate::arr::Arr;
+use crate::signal::{
+    butter, butter_sos, filtfilt, firwin, firwin_with, lfilter, lfilter_zi, sosfilt, sosfiltfilt,
+    FilterBand, Window,
+};
+
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+// Returns the gain of `b(z) / a(z)` at normalised frequency `w` (1 = Nyquist).
+fn gain(b: &[f64], a: &[f64], w: f64) -> f64 {
+    let eval = |c: &[f64]| -> Complex64 {
+        c.iter()
+            .enumerate()
+            .map(|(k, &v)| v * Complex64::from_polar(1.0, -PI * w * k as f64))
+            .sum()
+    };
+    (eval(b) / eval(a)).norm()
+}
+
+fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() <= tol, "index {}: {} != {}", i, x, y);
+    }
+}
+
+#[test]
+fn test_lfilter() {
+    let mut x = vec![0.0; 6];
+    x[0] = 1.0;
+    let (y, _) = lfilter(&[2.0], &[2.0, -1.0], &x, None);
+    assert_eq!(y.vec(), vec![1.0, 0.5, 0.25, 0.125, 0.0625, 0.03125]);
+
+    let x: Vec<f64> = (0..10).map(|i| (i as f64).sin()).collect();
+    let (b, a) = ([0.5, 0.3, 0.2], [1.0, -0.4]);
+    let (whole, _) = lfilter(&b, &a, &x, None);
+    let (first, z) = lfilter(&b, &a, &x[..4], None);
+    let (second, _) = lfilter(&b, &a, &x[4..], Some(&z));
+    assert_close(&whole[..4], &first, 1e-15);
+    assert_close(&whole[4..], &second, 1e-15);
+
+    // Steady state: a step input gives a constant output.
+    let zi = lfilter_zi(&b, &a);
+    let (y, _) = lfilter(&b, &a, &[1.0; 5], Some(&zi));
+    assert_close(&y, &[1.0 / 0.6; 5], 1e-12);
+
+    let c = [Complex64::new(1.0, 2.0), Complex64::new(0.0, 0.0)];
+    let (y, _) = lfilter(&[1.0], &[1.0, -0.5], &c, None);
+    assert_eq!(y[1], Complex64::new(0.5, 1.0));
+}
+
+#[test]
+fn test_butter() {
+    let (b, a) = butter(2, FilterBand::Lowpass(0.5));
+    assert_close(&b, &[0.29289322, 0.58578644, 0.29289322], 1e-8);
+    assert_close(&a, &[1.0, 0.0, 0.17157288], 1e-8);
+
+    let (b, a) = butter(4, FilterBand::Lowpass(0.2));
+    assert_close(
+        &b,
+        &[0.00482434, 0.01929737, 0.02894606, 0.01929737, 0.00482434],
+        1e-8,
+    );
+    assert_close(
+        &a,
+        &[1.0, -2.36951301, 2.31398841, -1.05466541, 0.18737949],
+        1e-8,
+    );
+    assert!((gain(&b, &a, 0.2) - 0.5f64.sqrt()).abs() < 1e-12);
+
+    let (b, a) = butter(2, FilterBand::Highpass(0.5));
+    assert_close(&b, &[0.29289322, -0.58578644, 0.29289322], 1e-8);
+    assert_close(&a, &[1.0, 0.0, 0.17157288], 1e-8);
+
+    let (b, a) = butter(3, FilterBand::Bandpass(0.2, 0.4));
+    assert_eq!(a.len(), 7);
+    assert!((gain(&b, &a, 0.2) - 0.5f64.sqrt()).abs() < 1e-12);
+    assert!((gain(&b, &a, 0.4) - 0.5f64.sqrt()).abs() < 1e-12);
+    assert!(gain(&b, &a, 0.0) < 1e-12 && gain(&b, &a, 1.0) < 1e-12);
+
+    let (b, a) = butter(3, FilterBand::Bandstop(0.2, 0.4));
+    assert!((gain(&b, &a, 0.0) - 1.0).abs() < 1e-12);
+    assert!((gain(&b, &a, 1.0) - 1.0).abs() < 1e-12);
+    assert!((gain(&b, &a, 0.4) - 0.5f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_sos() {
+    let x: Vec<f64> = (0..200)
+        .map(|i| (i as f64 * 0.3).sin() + (i as f64 * 2.5).cos())
+        .collect();
+    for (order, band) in [
+        (5, FilterBand::Lowpass(0.3)),
+        (4, FilterBand::Highpass(0.6)),
+        (3, FilterBand::Bandpass(0.1, 0.5)),
+        (2, FilterBand::Bandstop(0.3, 0.7)),
+    ] {
+        let (b, a) = butter(order, band);
+        let sos = butter_sos(order, band);
+        let (expected, _) = lfilter(&b, &a, &x, None);
+        let (y, zf) = sosfilt(&sos, &x, None);
+        assert_eq!(zf.len(), sos.len());
+        assert_close(&y, &expected, 1e-9);
+        assert_close(&sosfiltfilt(&sos, &x), &filtfilt(&b, &a, &x), 1e-8);
+    }
+}
+
+#[test]
+fn test_sos_pairing() {
+    // scipy.signal.butter(4, 0.2, output='sos')
+    let sos = butter_sos(4, FilterBand::Lowpass(0.2));
+    assert_eq!(sos.len(), 2);
+    let expected = [
+        [
+            0.00482434,
+            0.00964869,
+            0.00482434,
+            1.0,
+            -1.04859958,
+            0.29614036,
+        ],
+        [1.0, 2.0, 1.0, 1.0, -1.32091343, 0.63273879],
+    ];
+    for (s, e) in sos.iter().zip(&expected) {
+        assert_close(s, e, 1e-8);
+    }
+
+    // Sections are ordered by increasing pole radius, and each pairs a
+    // conjugate pair of poles with a conjugate pair of zeros on the
+    // unit circle.
+    let sos = butter_sos::<f64>(8, FilterBand::Bandstop(0.2, 0.25));
+    assert_eq!(sos.len(), 8);
+    assert!(sos.windows(2).all(|w| w[0][5] <= w[1][5]));
+    for s in &sos {
+        assert!((s[2] - s[0]).abs() < 1e-12 * s[0].abs());
+    }
+}
+
+#[test]
+fn test_filtfilt() {
+    // Zero phase: a low frequency passes without delay.
+    let x: Vec<f64> = (0..400)
+        .map(|i| (2.0 * PI * i as f64 / 100.0).sin())
+        .collect();
+    let noisy: Vec<f64> = x
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + 0.2 * (i as f64 * 2.9).sin())
+        .collect();
+    let (b, a) = butter(4, FilterBand::Lowpass(0.1));
+    let y = filtfilt(&b, &a, &noisy);
+    assert_eq!(y.len(), x.len());
+    assert_close(&y[50..350], &x[50..350], 1e-2);
+
+    let sos = butter_sos(4, FilterBand::Lowpass(0.1));
+    assert_close(&sosfiltfilt(&sos, &noisy)[50..350], &x[50..350], 1e-2);
+}
+
+#[test]
+fn test_firwin() {
+    let h = firwin(31, FilterBand::Lowpass(0.3));
+    assert_eq!(h.len(), 31);
+    assert_close(&h, &h.iter().rev().copied().collect::<Vec<_>>(), 1e-15);
+    assert!((h.sum() - 1.0).abs() < 1e-12);
+    assert!(gain(&h, &[1.0], 0.6) < 0.01);
+
+    let h = firwin(31, FilterBand::Highpass(0.3));
+    assert!((gain(&h, &[1.0], 1.0) - 1.0).abs() < 1e-12);
+    assert!(gain(&h, &[1.0], 0.0) < 0.01);
+
+    let h: Arr<f64> = firwin(41, FilterBand::Bandpass(0.2, 0.5));
+    assert!((gain(&h, &[1.0], 0.35) - 1.0).abs() < 1e-12);
+    let h = firwin(41, FilterBand::Bandstop(0.2, 0.5));
+    assert!((gain(&h, &[1.0], 0.0) - 1.0).abs() < 1e-12);
+    assert!(gain(&h, &[1.0], 0.35) < 0.05);
+}
+
+#[test]
+fn test_firwin_with() {
+    let h = firwin(31, FilterBand::Lowpass(0.3));
+    assert_close(
+        &firwin_with(31, FilterBand::Lowpass(0.3), Window::Hamming),
+        &h,
+        1e-15,
+    );
+
+    let h: Arr<f64> = firwin_with(31, FilterBand::Lowpass(0.3), Window::Kaiser(8.0));
+    assert!((h.sum() - 1.0).abs() < 1e-12);
+    assert!(gain(&h, &[1.0], 0.6) < 1e-3);
+}
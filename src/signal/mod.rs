@@ -0,0 +1,16 @@
+mod convolve;
+mod filter;
+mod spectral;
+mod window;
+
+#[cfg(test)]
+mod convolve_test;
+#[cfg(test)]
+mod filter_test;
+#[cfg(test)]
+mod spectral_test;
+
+pub use convolve::*;
+pub use filter::*;
+pub use spectral::*;
+pub use window::*;
@@ -0,0 +1,96 @@
+use crate::arr::Arr;
+use crate::fft::FftPlanner;
+use crate::signal::Window;
+use crate::traits::Float;
+
+use num_complex::Complex;
+
+/// Scaling of a power spectrum estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scaling {
+    /// Power spectral density, in units of `x^2 / Hz`.
+    #[default]
+    Density,
+    /// Power spectrum, in units of `x^2`.
+    Spectrum,
+}
+
+/// Returns the sample frequencies and one-sided power spectral density
+/// (or spectrum) of the real signal `x`, sampled at `fs`, using a single
+/// tapered segment. The mean is removed before the transform.
+pub fn periodogram<F>(x: &[F], fs: F, window: Window<F>, scaling: Scaling) -> (Arr<F>, Arr<F>)
+where
+    F: Float,
+{
+    welch(x, fs, window, x.len(), Some(0), scaling)
+}
+
+/// Returns the sample frequencies and one-sided power spectral density
+/// (or spectrum) of the real signal `x`, sampled at `fs`, estimated by
+/// Welch's method: averaging the periodograms of segments of `nperseg`
+/// values overlapping by `noverlap`, which defaults to `nperseg / 2`.
+/// Segments are detrended by removing their mean. If `x` is shorter
+/// than `nperseg` a single segment of its length is used.
+pub fn welch<F>(
+    x: &[F],
+    fs: F,
+    window: Window<F>,
+    nperseg: usize,
+    noverlap: Option<usize>,
+    scaling: Scaling,
+) -> (Arr<F>, Arr<F>)
+where
+    F: Float,
+{
+    assert!(!x.is_empty(), "input must not be empty");
+    let nperseg = nperseg.min(x.len());
+    assert!(nperseg > 0, "nperseg must be positive");
+    let noverlap = noverlap.unwrap_or(nperseg / 2);
+    assert!(noverlap < nperseg, "noverlap must be less than nperseg");
+
+    let w = window.periodic(nperseg);
+    let scale = match scaling {
+        Scaling::Density => (fs * w.iter().fold(F::zero(), |s, &v| s + v * v)).recip(),
+        Scaling::Spectrum => {
+            let sum = w.iter().fold(F::zero(), |s, &v| s + v);
+            (sum * sum).recip()
+        }
+    };
+
+    let plan = FftPlanner::new().plan(nperseg);
+    let nfreq = nperseg / 2 + 1;
+    let mut pxx = vec![F::zero(); nfreq];
+    let step = nperseg - noverlap;
+    let mut segments = 0;
+    for start in (0..=x.len() - nperseg).step_by(step) {
+        let seg = &x[start..start + nperseg];
+        let mean = seg.iter().fold(F::zero(), |s, &v| s + v) / F::from(nperseg).unwrap();
+        let mut buf: Vec<Complex<F>> = seg
+            .iter()
+            .zip(w.iter())
+            .map(|(&v, &w)| Complex::new((v - mean) * w, F::zero()))
+            .collect();
+        plan.forward(&mut buf);
+        pxx.iter_mut()
+            .zip(&buf)
+            .for_each(|(p, c)| *p = *p + c.norm_sqr());
+        segments += 1;
+    }
+
+    // Average, scale and fold the negative frequencies onto the
+    // positive ones, except for the DC and Nyquist terms.
+    let two = F::one() + F::one();
+    let norm = scale / F::from(segments).unwrap();
+    let last = if nperseg.is_multiple_of(2) {
+        nfreq - 1
+    } else {
+        nfreq
+    };
+    for (k, p) in pxx.iter_mut().enumerate() {
+        *p = *p * norm;
+        if k > 0 && k < last {
+            *p = *p * two;
+        }
+    }
+    (Arr::rfftfreq(nperseg, fs.recip()), Arr { values: pxx })
+}
@@ -0,0 +1,81 @@
+use crate::arr::Arr;
+use crate::assert_arr_close;
+use crate::signal::{periodogram, welch, Scaling, Window};
+
+use rand::Rng;
+use std::f64::consts::PI;
+
+#[test]
+fn test_windows() {
+    assert_arr_close!(Arr::hann(5), Arr::with_vec(vec![0.0, 0.5, 1.0, 0.5, 0.0]));
+    assert_arr_close!(
+        Arr::hamming(5),
+        Arr::with_vec(vec![0.08, 0.54, 1.0, 0.54, 0.08])
+    );
+    assert_arr_close!(
+        Arr::blackman(5),
+        Arr::with_vec(vec![0.0, 0.34, 1.0, 0.34, 0.0]),
+        1e-12,
+        1e-12
+    );
+    assert_arr_close!(
+        Arr::tukey(5, 0.5),
+        Arr::with_vec(vec![0.0, 1.0, 1.0, 1.0, 0.0])
+    );
+    assert_arr_close!(Arr::kaiser(4, 0.0), Arr::with_vec(vec![1.0; 4]));
+    assert_arr_close!(
+        Arr::kaiser(3, 14.0),
+        Arr::with_vec(vec![7.72686684e-06, 1.0, 7.72686684e-06])
+    );
+    let flattop = Arr::<f64>::flattop(5);
+    assert!((flattop[2] - 1.0).abs() < 1e-8 && flattop[0].abs() < 1e-3);
+
+    assert_arr_close!(
+        Window::Hann.periodic(4),
+        Arr::with_vec(vec![0.0, 0.5, 1.0, 0.5])
+    );
+    assert_eq!(Window::<f64>::Tukey(1.0).symmetric(7), Arr::hann(7));
+    assert_eq!(Arr::<f64>::hann(1).vec(), vec![1.0]);
+    assert!(Arr::<f64>::hann(0).is_empty());
+}
+
+#[test]
+fn test_periodogram() {
+    let (fs, n) = (100.0, 200);
+    let x: Vec<f64> = (0..n)
+        .map(|i| 3.0 * (2.0 * PI * 10.0 * i as f64 / fs).sin())
+        .collect();
+
+    let (f, p) = periodogram(&x, fs, Window::Boxcar, Scaling::Spectrum);
+    assert_eq!(f.len(), n / 2 + 1);
+    assert_eq!(f[20], 10.0);
+    assert!((p[20] - 4.5).abs() < 1e-9);
+    assert!(p.iter().enumerate().all(|(k, &v)| k == 20 || v < 1e-20));
+
+    // Parseval: the density integrates to the variance.
+    let (f, p) = periodogram(&x, fs, Window::Boxcar, Scaling::Density);
+    let df = f[1] - f[0];
+    assert!((p.sum() * df - 4.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_welch() {
+    let mut rng = rand::thread_rng();
+    let fs = 10.0;
+    let x: Vec<f64> = (0..20_000).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+    // White noise of variance 1/3 has a flat one-sided density 2 / 3 / fs.
+    let (f, p) = welch(&x, fs, Window::Hann, 256, None, Scaling::Density);
+    assert_eq!(f.len(), 129);
+    assert_eq!(f[128], 5.0);
+    let mean = p[1..128].iter().sum::<f64>() / 127.0;
+    assert!((mean - 2.0 / 3.0 / fs).abs() < 0.01);
+
+    let x: Vec<f64> = (0..1000)
+        .map(|i| (2.0 * PI * 1.25 * i as f64 / fs).sin())
+        .collect();
+    let (f, p) = welch(&x, fs, Window::Hann, 64, Some(16), Scaling::Spectrum);
+    let peak = p.argmax();
+    assert_eq!(f[peak], 1.25);
+    assert!((p[peak] - 0.5).abs() < 1e-9);
+}
@@ -0,0 +1,159 @@
+use crate::arr::Arr;
+use crate::traits::Float;
+
+use std::f64::consts::PI;
+
+/// Window functions for filter design and spectral analysis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window<F> {
+    Boxcar,
+    Hann,
+    Hamming,
+    Blackman,
+    /// Kaiser window with shape parameter `beta`.
+    Kaiser(F),
+    /// Tukey (tapered cosine) window with taper fraction `alpha`.
+    Tukey(F),
+    Flattop,
+}
+
+impl<F> Window<F>
+where
+    F: Float,
+{
+    /// Returns the `n` point symmetric window, for filter design.
+    pub fn symmetric(&self, n: usize) -> Arr<F> {
+        Arr {
+            values: self
+                .values(n)
+                .into_iter()
+                .map(|v| F::from(v).unwrap())
+                .collect(),
+        }
+    }
+
+    /// Returns the `n` point periodic window, for spectral analysis:
+    /// the first `n` points of the `n + 1` point symmetric window.
+    pub fn periodic(&self, n: usize) -> Arr<F> {
+        let mut w = self.values(n + 1);
+        w.truncate(n);
+        Arr {
+            values: w.into_iter().map(|v| F::from(v).unwrap()).collect(),
+        }
+    }
+
+    fn values(&self, n: usize) -> Vec<f64> {
+        if n <= 1 {
+            return vec![1.0; n];
+        }
+        let m = (n - 1) as f64;
+        match *self {
+            Window::Boxcar => vec![1.0; n],
+            Window::Hann => general_cosine(n, &[0.5, 0.5]),
+            Window::Hamming => general_cosine(n, &[0.54, 0.46]),
+            Window::Blackman => general_cosine(n, &[0.42, 0.5, 0.08]),
+            Window::Flattop => general_cosine(
+                n,
+                &[
+                    0.21557895,
+                    0.41663158,
+                    0.277263158,
+                    0.083578947,
+                    0.006947368,
+                ],
+            ),
+            Window::Kaiser(beta) => {
+                let beta = beta.to_f64().unwrap();
+                (0..n)
+                    .map(|i| {
+                        let r = 2.0 * i as f64 / m - 1.0;
+                        bessel_i0(beta * (1.0 - r * r).max(0.0).sqrt()) / bessel_i0(beta)
+                    })
+                    .collect()
+            }
+            Window::Tukey(alpha) => {
+                let alpha = alpha.to_f64().unwrap();
+                if alpha <= 0.0 {
+                    return vec![1.0; n];
+                }
+                if alpha >= 1.0 {
+                    return Window::<F>::Hann.values(n);
+                }
+                let width = (alpha * m / 2.0).floor() as usize;
+                (0..n)
+                    .map(|i| {
+                        let x = i as f64;
+                        if i <= width {
+                            0.5 * (1.0 + (PI * (-1.0 + 2.0 * x / (alpha * m))).cos())
+                        } else if i < n - width - 1 {
+                            1.0
+                        } else {
+                            0.5 * (1.0 + (PI * (-2.0 / alpha + 1.0 + 2.0 * x / (alpha * m))).cos())
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+// Returns the sum of `a[k] * cos(k * x)` for `x` spaced from -pi to pi.
+fn general_cosine(n: usize, a: &[f64]) -> Vec<f64> {
+    (0..n)
+        .map(|i| {
+            let x = -PI + 2.0 * PI * i as f64 / (n - 1) as f64;
+            a.iter()
+                .enumerate()
+                .map(|(k, &a)| a * (k as f64 * x).cos())
+                .sum()
+        })
+        .collect()
+}
+
+// Modified Bessel function of the first kind, order zero, by its
+// power series, which converges for all `x`.
+fn bessel_i0(x: f64) -> f64 {
+    let q = x * x / 4.0;
+    let (mut sum, mut term, mut k) = (1.0, 1.0, 1.0);
+    while term > sum * f64::EPSILON {
+        term *= q / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+impl<F> Arr<F>
+where
+    F: Float,
+{
+    /// Returns the `n` point symmetric Hann window.
+    pub fn hann(n: usize) -> Self {
+        Window::Hann.symmetric(n)
+    }
+
+    /// Returns the `n` point symmetric Hamming window.
+    pub fn hamming(n: usize) -> Self {
+        Window::Hamming.symmetric(n)
+    }
+
+    /// Returns the `n` point symmetric Blackman window.
+    pub fn blackman(n: usize) -> Self {
+        Window::Blackman.symmetric(n)
+    }
+
+    /// Returns the `n` point symmetric Kaiser window with shape `beta`.
+    pub fn kaiser(n: usize, beta: F) -> Self {
+        Window::Kaiser(beta).symmetric(n)
+    }
+
+    /// Returns the `n` point symmetric Tukey window with taper fraction `alpha`.
+    pub fn tukey(n: usize, alpha: F) -> Self {
+        Window::Tukey(alpha).symmetric(n)
+    }
+
+    /// Returns the `n` point symmetric flat top window.
+    pub fn flattop(n: usize) -> Self {
+        Window::Flattop.symmetric(n)
+    }
+}
@@ -1,6 +1,10 @@
-use crate::traits::{IsNaN, Norm, Sqrt};
+use crate::error::Error;
+use crate::mat::Mat;
+use crate::stats::{Bins, QuantileMethod};
+use crate::traits::{Float, IsNaN, Norm, Sqrt};
 use num_traits::bounds::Bounded;
-use num_traits::{FromPrimitive, One, Pow, ToPrimitive, Zero};
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub};
 
 /// Returns a vector with the indexes of the nonzero elements of `a`.
@@ -308,13 +312,26 @@ pub fn argsort<T>(a: &[T], reverse: bool) -> Vec<usize>
 where
     T: PartialOrd + Copy,
 {
+    try_argsort(a, reverse).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the indexes that sort `a` into ascending order,
+/// or an `Unordered` error if any values cannot be compared.
+pub fn try_argsort<T>(a: &[T], reverse: bool) -> Result<Vec<usize>, Error>
+where
+    T: PartialOrd + Copy,
+{
+    // Check before sorting, as the sort may panic if the comparison
+    // is not a total order.
+    if a.iter().any(|v| v.partial_cmp(v).is_none()) {
+        return Err(Error::Unordered);
+    }
     let mut ix: Vec<usize> = (0..a.len()).collect();
-    // ix.sort_by_key(|&i| a[i]);
     ix.sort_unstable_by(|&i, &j| a[i].partial_cmp(&a[j]).unwrap());
     if reverse {
         ix.reverse()
     }
-    ix
+    Ok(ix)
 }
 
 /// Computes the infinity norm: `max(abs(a))`
@@ -332,6 +349,51 @@ where
     max
 }
 
+/// Returns true if `a` and `b` have the same length and all elements
+/// satisfy `norm(a[i] - b[i]) <= atol + rtol * norm(b[i])`.
+pub fn allclose<T, F>(a: &[T], b: &[T], rtol: F, atol: F) -> bool
+where
+    T: Copy + PartialEq + Sub<Output = T> + Norm<F>,
+    F: Float,
+{
+    a.len() == b.len() && worst_not_close(a, b, rtol, atol).is_none()
+}
+
+/// Returns the index of the element that most exceeds the tolerance
+/// `atol + rtol * norm(b[i])`, or `None` if all elements of `a` and `b`
+/// are close. NaN values are never close.
+pub fn worst_not_close<T, F>(a: &[T], b: &[T], rtol: F, atol: F) -> Option<usize>
+where
+    T: Copy + PartialEq + Sub<Output = T> + Norm<F>,
+    F: Float,
+{
+    assert_eq!(a.len(), b.len());
+
+    let mut worst = None;
+    let mut max_excess = F::zero();
+    for (i, (&x, &y)) in a.iter().zip(b).enumerate() {
+        // Equal infinities are close, though their difference is NaN.
+        if x == y {
+            continue;
+        }
+        let diff = (x - y).norm();
+        let tol = atol + rtol * y.norm();
+        if diff <= tol {
+            continue;
+        }
+        let excess = if diff.is_nan() {
+            F::infinity()
+        } else {
+            diff - tol
+        };
+        if worst.is_none() || excess > max_excess {
+            worst = Some(i);
+            max_excess = excess;
+        }
+    }
+    worst
+}
+
 // pub fn norm_inf(a: ArrayView1<f64>) -> f64 {
 //     a.iter()
 //         .max_by(|&a, &b| a.partial_cmp(b).unwrap())
@@ -357,17 +419,45 @@ where
     ix
 }
 
+/// Returns the index of the maximum value of `a`, or an
+/// error if `a` is empty or any values cannot be compared.
+pub fn try_argmax<T>(a: &[T]) -> Result<usize, Error>
+where
+    T: PartialOrd + Copy,
+{
+    let mut ix = 0;
+    for (i, v) in a.iter().enumerate() {
+        if v.partial_cmp(&a[ix]).ok_or(Error::Unordered)? == Ordering::Greater {
+            ix = i;
+        }
+    }
+    if a.is_empty() {
+        return Err(Error::Empty);
+    }
+    Ok(ix)
+}
+
 /// Sums the values of `a`.
 pub fn sum<T>(a: &[T]) -> T
 where
     T: Zero + Copy + AddAssign,
 {
-    assert_ne!(a.len(), 0);
+    try_sum(a).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Sums the values of `a`, or returns an `Empty` error.
+pub fn try_sum<T>(a: &[T]) -> Result<T, Error>
+where
+    T: Zero + Copy + AddAssign,
+{
+    if a.is_empty() {
+        return Err(Error::Empty);
+    }
     let mut sum = T::zero();
     for &v in a {
         sum += v;
     }
-    sum
+    Ok(sum)
 }
 
 /// Returns the mean of `a`.
@@ -375,33 +465,212 @@ pub fn mean<T>(a: &[T]) -> T
 where
     T: Zero + Copy + AddAssign + Div<Output = T> + FromPrimitive,
 {
-    assert_ne!(a.len(), 0);
-    // let n = a.len();
-    // if n == 0 {
-    // 	return 0
-    // }
-    sum(a) / T::from_usize(a.len()).unwrap()
+    try_mean(a).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the mean of `a`, or an `Empty` error.
+pub fn try_mean<T>(a: &[T]) -> Result<T, Error>
+where
+    T: Zero + Copy + AddAssign + Div<Output = T> + FromPrimitive,
+{
+    Ok(try_sum(a)? / T::from_usize(a.len()).unwrap())
 }
 
-/// Returns the standard deviation of `a` defined as: `Sqrt(Mean((x - Mean(x))^2))`
-pub fn std<T>(a: &[T]) -> T
+/// Returns the variance of `a` defined as `Sum((x - Mean(x))^2) / (N - ddof)`.
+pub fn var<T>(a: &[T], ddof: usize) -> T
+where
+    T: Zero
+        + Copy
+        + AddAssign
+        + Div<Output = T>
+        + FromPrimitive
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    try_var(a, ddof).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the variance of `a`, or an `Empty` error.
+pub fn try_var<T>(a: &[T], ddof: usize) -> Result<T, Error>
 where
     T: Zero
         + Copy
         + AddAssign
         + Div<Output = T>
-        + Sqrt
         + FromPrimitive
-        + Pow<usize, Output = T>
+        + Mul<Output = T>
         + Sub<Output = T>,
 {
-    let mean = mean(a);
+    let mean = try_mean(a)?;
     let mut sum = T::zero();
     for &v in a {
-        sum += T::pow(v - mean, 2);
+        sum += (v - mean) * (v - mean);
+    }
+    Ok(sum / T::from_usize(a.len().saturating_sub(ddof)).unwrap())
+}
+
+/// Returns the standard deviation of `a` defined as `Sqrt(Var(x, ddof))`.
+pub fn std<T>(a: &[T], ddof: usize) -> T
+where
+    T: Zero
+        + Copy
+        + AddAssign
+        + Div<Output = T>
+        + Sqrt
+        + FromPrimitive
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    try_std(a, ddof).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the standard deviation of `a`, or an `Empty` error.
+pub fn try_std<T>(a: &[T], ddof: usize) -> Result<T, Error>
+where
+    T: Zero
+        + Copy
+        + AddAssign
+        + Div<Output = T>
+        + Sqrt
+        + FromPrimitive
+        + Mul<Output = T>
+        + Sub<Output = T>,
+{
+    Ok(T::sqrt(&try_var(a, ddof)?))
+}
+
+/// Returns the median of `a`. The result is NaN if `a` contains NaN.
+pub fn median<T>(a: &[T]) -> T
+where
+    T: Float,
+{
+    try_median(a).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the median of `a`, or an `Empty` error.
+pub fn try_median<T>(a: &[T]) -> Result<T, Error>
+where
+    T: Float,
+{
+    try_quantile(a, 0.5, QuantileMethod::Linear)
+}
+
+/// Returns quantile `q` of `a`, for `q` in `[0, 1]`, estimated using `method`.
+/// The result is NaN if `a` contains NaN.
+pub fn quantile<T>(a: &[T], q: f64, method: QuantileMethod) -> T
+where
+    T: Float,
+{
+    try_quantile(a, q, method).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns quantile `q` of `a`, or an `Empty` error, or an `OutOfRange`
+/// error if `q` is not in `[0, 1]`.
+pub fn try_quantile<T>(a: &[T], q: f64, method: QuantileMethod) -> Result<T, Error>
+where
+    T: Float,
+{
+    if !(0.0..=1.0).contains(&q) {
+        return Err(Error::OutOfRange);
+    }
+    if a.is_empty() {
+        return Err(Error::Empty);
     }
-    let variance = sum / T::from_usize(a.len()).unwrap();
-    T::sqrt(&variance)
+    if a.iter().any(|v| v.is_nan()) {
+        return Ok(T::nan());
+    }
+    let (i, g) = method.position(a.len(), q);
+
+    // Partition a copy so that the order statistics `i` and `i + 1`
+    // are `v[i]` and the minimum of `v[i + 1..]`.
+    let mut v = a.to_vec();
+    let (_, &mut lo, above) = v.select_nth_unstable_by(i, |x, y| x.partial_cmp(y).unwrap());
+    if g == 0.0 {
+        return Ok(lo);
+    }
+    let hi = above.iter().copied().fold(T::infinity(), T::min);
+    Ok(lerp(lo, hi, T::from(g).unwrap()))
+}
+
+/// Returns percentile `p` of `a`, for `p` in `[0, 100]`.
+pub fn percentile<T>(a: &[T], p: f64, method: QuantileMethod) -> T
+where
+    T: Float,
+{
+    quantile(a, p / 100.0, method)
+}
+
+// Interpolates from the nearer end point for accuracy, as NumPy does.
+fn lerp<T: Float>(a: T, b: T, t: T) -> T {
+    let d = b - a;
+    if t >= T::from(0.5).unwrap() {
+        b - d * (T::one() - t)
+    } else {
+        a + d * t
+    }
+}
+
+/// Returns the most frequent value of `a`, the smallest if tied.
+pub fn mode<T>(a: &[T]) -> T
+where
+    T: Float + IsNaN,
+{
+    try_mode(a).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the most frequent value of `a`, or an `Empty` error.
+/// NaNs are never equal and so are only the mode if all values are NaN.
+pub fn try_mode<T>(a: &[T]) -> Result<T, Error>
+where
+    T: Float + IsNaN,
+{
+    let mut v = a.to_vec();
+    v.sort_unstable_by(cmp_nan_last);
+
+    let mut best = (*v.first().ok_or(Error::Empty)?, 0);
+    let mut start = 0;
+    for i in 1..=v.len() {
+        if i == v.len() || v[i] != v[start] {
+            if i - start > best.1 {
+                best = (v[start], i - start);
+            }
+            start = i;
+        }
+    }
+    Ok(best.0)
+}
+
+/// Returns the (biased) sample skewness `m3 / m2^1.5`, where
+/// `mk` is the k-th central moment.
+pub fn skewness<T>(a: &[T]) -> T
+where
+    T: Float,
+{
+    let (m2, m3, _) = central_moments(a);
+    m3 / m2.powf(T::from(1.5).unwrap())
+}
+
+/// Returns the (biased) excess kurtosis `m4 / m2^2 - 3`, where
+/// `mk` is the k-th central moment.
+pub fn kurtosis<T>(a: &[T]) -> T
+where
+    T: Float,
+{
+    let (m2, _, m4) = central_moments(a);
+    m4 / (m2 * m2) - T::from(3).unwrap()
+}
+
+fn central_moments<T: Float>(a: &[T]) -> (T, T, T) {
+    let n = T::from(a.len()).unwrap();
+    let mean = a.iter().fold(T::zero(), |s, &v| s + v) / n;
+    let (mut m2, mut m3, mut m4) = (T::zero(), T::zero(), T::zero());
+    for &v in a {
+        let d = v - mean;
+        m2 = m2 + d * d;
+        m3 = m3 + d * d * d;
+        m4 = m4 + d * d * d * d;
+    }
+    (m2 / n, m3 / n, m4 / n)
 }
 
 /// Returns an array with values linearly spaced
@@ -457,17 +726,155 @@ pub fn diff<T>(a: &[T]) -> Vec<T>
 where
     T: Zero + Copy + Sub<Output = T>,
 {
-    assert_ne!(a.len(), 0);
-    let n = a.len();
-    if n == 1 {
-        return vec![];
+    try_diff(a).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the discrete difference of `a`, or an `Empty` error.
+pub fn try_diff<T>(a: &[T]) -> Result<Vec<T>, Error>
+where
+    T: Zero + Copy + Sub<Output = T>,
+{
+    if a.is_empty() {
+        return Err(Error::Empty);
     }
+    Ok(a.windows(2).map(|w| w[1] - w[0]).collect())
+}
 
-    let mut b = vec![T::zero(); n - 1];
-    for i in 1..n {
-        b[i - 1] = a[i] - a[i - 1];
+/// Spacing of sample points for integration and differentiation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Spacing<'a, T> {
+    /// Evenly spaced points a constant distance apart.
+    Uniform(T),
+    /// Increasing sample point coordinates, one per value.
+    Coords(&'a [T]),
+}
+
+impl<T: Float> Spacing<'_, T> {
+    // Returns the widths of the `n - 1` intervals between `n` points.
+    fn widths(&self, n: usize) -> Vec<T> {
+        match *self {
+            Spacing::Uniform(dx) => vec![dx; n.saturating_sub(1)],
+            Spacing::Coords(x) => {
+                assert_eq!(
+                    x.len(),
+                    n,
+                    "coordinates must have the same length as the values"
+                );
+                x.windows(2).map(|w| w[1] - w[0]).collect()
+            }
+        }
     }
-    b
+}
+
+/// Integrates the samples `y` using the composite trapezoidal rule.
+pub fn trapz<T: Float>(y: &[T], spacing: Spacing<T>) -> T {
+    let two = T::one() + T::one();
+    spacing
+        .widths(y.len())
+        .iter()
+        .zip(y.windows(2))
+        .fold(T::zero(), |s, (&h, w)| s + h * (w[0] + w[1]) / two)
+}
+
+/// Returns the cumulative integral of the samples `y` using the
+/// trapezoidal rule. The result has one value less than `y` unless
+/// `initial` is given, in which case it is prepended. The result is
+/// empty if `y` is.
+pub fn cumtrapz<T: Float>(y: &[T], spacing: Spacing<T>, initial: Option<T>) -> Vec<T> {
+    let two = T::one() + T::one();
+    let mut total = T::zero();
+    initial
+        .filter(|_| !y.is_empty())
+        .into_iter()
+        .chain(
+            spacing
+                .widths(y.len())
+                .iter()
+                .zip(y.windows(2))
+                .map(|(&h, w)| {
+                    total = total + h * (w[0] + w[1]) / two;
+                    total
+                }),
+        )
+        .collect()
+}
+
+/// Integrates the samples `y` using the composite Simpson's rule, which
+/// is exact for quadratics. With an even number of points the last
+/// interval is integrated using the parabola through the last three
+/// points, as in SciPy.
+pub fn simpson<T: Float>(y: &[T], spacing: Spacing<T>) -> T {
+    let n = y.len();
+    let h = spacing.widths(n);
+    if n < 3 {
+        return trapz(y, spacing);
+    }
+    let two = T::one() + T::one();
+    let six = two * (two + T::one());
+
+    let odd = if n % 2 == 1 { n } else { n - 1 };
+    let mut total = T::zero();
+    for i in (0..odd - 2).step_by(2) {
+        let (h0, h1) = (h[i], h[i + 1]);
+        let sum = h0 + h1;
+        total = total
+            + sum / six
+                * ((two - h1 / h0) * y[i]
+                    + sum * sum / (h0 * h1) * y[i + 1]
+                    + (two - h0 / h1) * y[i + 2]);
+    }
+    if odd < n {
+        let (h0, h1) = (h[n - 3], h[n - 2]);
+        let three = two + T::one();
+        let alpha = (two * h1 * h1 + three * h0 * h1) / (six * (h0 + h1));
+        let beta = (h1 * h1 + three * h0 * h1) / (six * h0);
+        let eta = h1 * h1 * h1 / (six * h0 * (h0 + h1));
+        total = total + alpha * y[n - 1] + beta * y[n - 2] - eta * y[n - 3];
+    }
+    total
+}
+
+/// Returns the gradient of the samples `y` using second order accurate
+/// central differences in the interior, which allow uneven spacing, and
+/// one sided differences of `edge_order` 1 or 2 at the ends, as NumPy.
+///
+/// Panics if `y` has fewer than `edge_order + 1` values.
+pub fn gradient<T: Float>(y: &[T], spacing: Spacing<T>, edge_order: usize) -> Vec<T> {
+    assert!(
+        edge_order == 1 || edge_order == 2,
+        "edge order must be 1 or 2"
+    );
+    let n = y.len();
+    assert!(
+        n > edge_order,
+        "at least {} values are required",
+        edge_order + 1
+    );
+    let h = spacing.widths(n);
+    let two = T::one() + T::one();
+
+    let mut g = vec![T::zero(); n];
+    for i in 1..n - 1 {
+        let (hs, hd) = (h[i - 1], h[i]);
+        g[i] = (hs * hs * y[i + 1] + (hd * hd - hs * hs) * y[i] - hd * hd * y[i - 1])
+            / (hs * hd * (hd + hs));
+    }
+    if edge_order == 1 {
+        g[0] = (y[1] - y[0]) / h[0];
+        g[n - 1] = (y[n - 1] - y[n - 2]) / h[n - 2];
+    } else {
+        let (h1, h2) = (h[0], h[1]);
+        let a = -(two * h1 + h2) / (h1 * (h1 + h2));
+        let b = (h1 + h2) / (h1 * h2);
+        let c = -h1 / (h2 * (h1 + h2));
+        g[0] = a * y[0] + b * y[1] + c * y[2];
+        let (h1, h2) = (h[n - 3], h[n - 2]);
+        let a = h2 / (h1 * (h1 + h2));
+        let b = -(h2 + h1) / (h1 * h2);
+        let c = (two * h2 + h1) / (h2 * (h1 + h2));
+        g[n - 1] = a * y[n - 3] + b * y[n - 2] + c * y[n - 1];
+    }
+    g
 }
 
 /// Returns the 2-norm (Euclidean) of `a`.
@@ -487,20 +894,16 @@ pub fn max<T>(a: &[T]) -> T
 where
     T: Bounded + PartialOrd + Copy,
 {
-    // assert_ne!(self.len(), 0);
-    //
-    // let mut v = T::min_value();
-    // for &x in &self.data {
-    //     if x > v {
-    //         v = x;
-    //     }
-    // }
-    // v
+    try_max(a).unwrap_or_else(|err| panic!("{}", err))
+}
 
-    a.iter()
-        .map(|&v| v)
-        .max_by(|&a, b| a.partial_cmp(b).unwrap())
-        .unwrap()
+/// Returns the maximum value of `a`, or an error if
+/// `a` is empty or any values cannot be compared.
+pub fn try_max<T>(a: &[T]) -> Result<T, Error>
+where
+    T: PartialOrd + Copy,
+{
+    Ok(a[try_argmax(a)?])
 }
 
 /// Returns the minimum value of `a`.
@@ -508,20 +911,22 @@ pub fn min<T>(a: &[T]) -> T
 where
     T: Bounded + PartialOrd + Copy,
 {
-    // assert_ne!(self.len(), 0);
-    //
-    // let mut v = T::max_value();
-    // for &x in &self.data {
-    //     if x < v {
-    //         v = x;
-    //     }
-    // }
-    // v
+    try_min(a).unwrap_or_else(|err| panic!("{}", err))
+}
 
-    a.iter()
-        .map(|&v| v)
-        .min_by(|&a, b| a.partial_cmp(b).unwrap())
-        .unwrap()
+/// Returns the minimum value of `a`, or an error if
+/// `a` is empty or any values cannot be compared.
+pub fn try_min<T>(a: &[T]) -> Result<T, Error>
+where
+    T: PartialOrd + Copy,
+{
+    let mut min = *a.first().ok_or(Error::Empty)?;
+    for &v in a {
+        if v.partial_cmp(&min).ok_or(Error::Unordered)? == Ordering::Less {
+            min = v;
+        }
+    }
+    Ok(min)
 }
 
 /// Returns an integer array with 1s where `T::is_nan(self[i])`.
@@ -537,3 +942,246 @@ where
     }
     b
 }
+
+/// Returns the sum of the values of `a` that are not NaN.
+pub fn nansum<T>(a: &[T]) -> T
+where
+    T: Zero + Copy + AddAssign + IsNaN,
+{
+    let mut sum = T::zero();
+    for v in a.iter().filter(|v| !v.is_nan()) {
+        sum += *v;
+    }
+    sum
+}
+
+/// Returns the mean of the values of `a` that are not NaN,
+/// or NaN if there are none.
+pub fn nanmean<T>(a: &[T]) -> T
+where
+    T: Zero + Copy + AddAssign + Div<Output = T> + FromPrimitive + IsNaN,
+{
+    let n = a.iter().filter(|v| !v.is_nan()).count();
+    // Zero divided by zero is NaN.
+    nansum(a) / T::from_usize(n).unwrap()
+}
+
+/// Returns the index of the maximum value of `a` ignoring NaNs,
+/// or `None` if all values are NaN.
+pub fn nanargmax<T>(a: &[T]) -> Option<usize>
+where
+    T: PartialOrd + IsNaN,
+{
+    nanargbest(a, Ordering::Greater)
+}
+
+/// Returns the index of the minimum value of `a` ignoring NaNs,
+/// or `None` if all values are NaN.
+pub fn nanargmin<T>(a: &[T]) -> Option<usize>
+where
+    T: PartialOrd + IsNaN,
+{
+    nanargbest(a, Ordering::Less)
+}
+
+fn nanargbest<T>(a: &[T], order: Ordering) -> Option<usize>
+where
+    T: PartialOrd + IsNaN,
+{
+    let mut best: Option<usize> = None;
+    for (i, v) in a.iter().enumerate() {
+        if v.is_nan() {
+            continue;
+        }
+        if best.is_none_or(|j| v.partial_cmp(&a[j]) == Some(order)) {
+            best = Some(i);
+        }
+    }
+    best
+}
+
+/// Returns the maximum value of `a` ignoring NaNs,
+/// or NaN if all values are NaN.
+pub fn nanmax<T>(a: &[T]) -> T
+where
+    T: PartialOrd + Copy + IsNaN + Zero + Div<Output = T>,
+{
+    nanargmax(a).map_or(T::zero() / T::zero(), |i| a[i])
+}
+
+/// Returns the minimum value of `a` ignoring NaNs,
+/// or NaN if all values are NaN.
+pub fn nanmin<T>(a: &[T]) -> T
+where
+    T: PartialOrd + Copy + IsNaN + Zero + Div<Output = T>,
+{
+    nanargmin(a).map_or(T::zero() / T::zero(), |i| a[i])
+}
+
+/// Compares values in ascending order with NaNs last.
+pub fn cmp_nan_last<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd + IsNaN,
+{
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Returns the indexes that stably sort `a` into ascending
+/// order with NaNs last.
+pub fn argsort_total<T>(a: &[T]) -> Vec<usize>
+where
+    T: PartialOrd + IsNaN,
+{
+    let mut ix: Vec<usize> = (0..a.len()).collect();
+    ix.sort_by(|&i, &j| cmp_nan_last(&a[i], &a[j]));
+    ix
+}
+
+/// Returns the counts of `a` in each bin and the bin edges. With
+/// `Bins::Count` the bins span `range`, or the range of `a` if `None`.
+/// Bins are half-open except the last, which includes its right edge.
+/// Values outside the bins, and NaNs, are not counted.
+pub fn histogram<T>(
+    a: &[T],
+    bins: impl Into<Bins<T>>,
+    range: Option<(T, T)>,
+) -> (Vec<usize>, Vec<T>)
+where
+    T: Float + FromPrimitive + AddAssign + DivAssign + MulAssign,
+{
+    let edges = bin_edges(a, bins.into(), range);
+    let mut counts = vec![0; edges.len().saturating_sub(1)];
+    for &v in a {
+        if let Some(i) = bin_index(v, &edges) {
+            counts[i] += 1;
+        }
+    }
+    (counts, edges)
+}
+
+/// Returns the 2-D histogram of the points `(x[i], y[i])` as a matrix
+/// with `x` bins along the rows and `y` bins along the columns, and the
+/// bin edges of each dimension.
+pub fn histogram2d<T>(
+    x: &[T],
+    y: &[T],
+    xbins: impl Into<Bins<T>>,
+    ybins: impl Into<Bins<T>>,
+    range: Option<((T, T), (T, T))>,
+) -> (Mat<usize>, Vec<T>, Vec<T>)
+where
+    T: Float + FromPrimitive + AddAssign + DivAssign + MulAssign,
+{
+    assert_eq!(x.len(), y.len());
+    let xedges = bin_edges(x, xbins.into(), range.map(|r| r.0));
+    let yedges = bin_edges(y, ybins.into(), range.map(|r| r.1));
+    let rows = xedges.len().saturating_sub(1);
+    let cols = yedges.len().saturating_sub(1);
+    let mut counts = vec![0; rows * cols];
+    for (&u, &v) in x.iter().zip(y) {
+        if let (Some(i), Some(j)) = (bin_index(u, &xedges), bin_index(v, &yedges)) {
+            counts[i * cols + j] += 1;
+        }
+    }
+    (Mat::new(rows, cols, counts, false), xedges, yedges)
+}
+
+fn bin_edges<T>(a: &[T], bins: Bins<T>, range: Option<(T, T)>) -> Vec<T>
+where
+    T: Float + FromPrimitive + AddAssign + DivAssign + MulAssign,
+{
+    match bins {
+        Bins::Count(n) => {
+            assert!(n > 0, "number of bins must be positive");
+            let (lo, hi) = range.unwrap_or_else(|| {
+                let finite = a.iter().filter(|v| v.is_finite());
+                let lo = finite.clone().fold(T::infinity(), |m, &v| m.min(v));
+                let hi = finite.fold(T::neg_infinity(), |m, &v| m.max(v));
+                if lo > hi {
+                    (T::zero(), T::one())
+                } else {
+                    (lo, hi)
+                }
+            });
+            assert!(lo <= hi, "histogram range must be increasing");
+            let (lo, hi) = if lo == hi {
+                let half = T::from_f64(0.5).unwrap();
+                (lo - half, hi + half)
+            } else {
+                (lo, hi)
+            };
+            linspace(lo, hi, n + 1, true)
+        }
+        Bins::Edges(edges) => {
+            assert!(
+                edges.windows(2).all(|w| w[0] <= w[1]),
+                "bin edges must increase monotonically"
+            );
+            edges
+        }
+    }
+}
+
+// Returns the bin of `v`, the last bin being closed.
+fn bin_index<T: Float>(v: T, edges: &[T]) -> Option<usize> {
+    let (&first, &last) = (edges.first()?, edges.last()?);
+    if edges.len() < 2 || !(first <= v && v <= last) {
+        return None;
+    }
+    if v == last {
+        return Some(edges.len() - 2);
+    }
+    Some(edges.partition_point(|&e| e <= v) - 1)
+}
+
+/// Returns the number of occurrences of each value in `x`. The result
+/// has length `max(x) + 1`, or `min_length` if greater.
+pub fn bincount(x: &[usize], min_length: usize) -> Vec<usize> {
+    let len = x.iter().map(|&i| i + 1).max().unwrap_or(0).max(min_length);
+    let mut counts = vec![0; len];
+    x.iter().for_each(|&i| counts[i] += 1);
+    counts
+}
+
+/// Returns the sum of the `weights` of each value in `x`.
+pub fn bincount_weighted<T>(x: &[usize], weights: &[T], min_length: usize) -> Vec<T>
+where
+    T: Zero + Copy + AddAssign,
+{
+    assert_eq!(x.len(), weights.len());
+    let len = x.iter().map(|&i| i + 1).max().unwrap_or(0).max(min_length);
+    let mut sums = vec![T::zero(); len];
+    x.iter().zip(weights).for_each(|(&i, &w)| sums[i] += w);
+    sums
+}
+
+/// Returns the index of the bin of each value of `x` given monotonic
+/// bin `edges`, such that `edges[i - 1] <= x < edges[i]` for increasing
+/// edges, or `edges[i - 1] > x >= edges[i]` for decreasing edges. If
+/// `right`, the bins are closed on the right instead. Values below the
+/// first edge give `0` and values beyond the last give `edges.len()`,
+/// as do NaNs.
+pub fn digitize<T>(x: &[T], edges: &[T], right: bool) -> Vec<usize>
+where
+    T: Float,
+{
+    let increasing = edges.windows(2).all(|w| w[0] <= w[1]);
+    assert!(
+        increasing || edges.windows(2).all(|w| w[0] >= w[1]),
+        "bin edges must be monotonic"
+    );
+    x.iter()
+        .map(|&v| match (v.is_nan(), increasing, right) {
+            (true, _, _) => edges.len(),
+            (false, true, false) => edges.partition_point(|&e| e <= v),
+            (false, true, true) => edges.partition_point(|&e| e < v),
+            (false, false, false) => edges.partition_point(|&e| e > v),
+            (false, false, true) => edges.partition_point(|&e| e >= v),
+        })
+        .collect()
+}
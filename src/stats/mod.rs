@@ -0,0 +1,126 @@
+mod running;
+mod tdigest;
+
+#[cfg(test)]
+mod running_test;
+
+pub use running::*;
+pub use tdigest::*;
+
+/// Methods for estimating a quantile from a sample, following NumPy's
+/// `quantile(method=...)` and Hyndman & Fan (1996).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    /// Hyndman & Fan type 1: the smallest value with `F(x) >= q`.
+    InvertedCdf,
+    /// Type 2: as `InvertedCdf`, averaging at discontinuities.
+    AveragedInvertedCdf,
+    /// Type 3: the nearest order statistic, ties to even.
+    ClosestObservation,
+    /// Type 4: linear interpolation of the empirical CDF.
+    InterpolatedInvertedCdf,
+    /// Type 5: piecewise linear with nodes at `(k - 0.5) / n`.
+    Hazen,
+    /// Type 6: nodes at `k / (n + 1)`.
+    Weibull,
+    /// Type 7: nodes at `(k - 1) / (n - 1)`.
+    #[default]
+    Linear,
+    /// Type 8: approximately median-unbiased.
+    MedianUnbiased,
+    /// Type 9: approximately unbiased for normal samples.
+    NormalUnbiased,
+    /// The order statistic below the linear position.
+    Lower,
+    /// The order statistic above the linear position.
+    Higher,
+    /// The mean of `Lower` and `Higher`.
+    Midpoint,
+    /// The order statistic nearest the linear position, ties to even.
+    Nearest,
+}
+
+impl QuantileMethod {
+    /// Returns `(i, g)` such that quantile `q` of a sorted sample `x` of
+    /// length `n > 0` is `(1 - g) * x[i] + g * x[i + 1]`, with `i < n` and
+    /// `g == 0` when `i == n - 1`.
+    pub(crate) fn position(self, n: usize, q: f64) -> (usize, f64) {
+        use QuantileMethod::*;
+
+        let nf = n as f64;
+        let (index, g) = match self {
+            InvertedCdf => {
+                let v = nf * q - 1.0;
+                let g = if v.fract() == 0.0 { 0.0 } else { 1.0 };
+                (v.floor(), g)
+            }
+            AveragedInvertedCdf => {
+                let v = nf * q - 1.0;
+                let g = if v.fract() == 0.0 { 0.5 } else { 1.0 };
+                (v.floor(), g)
+            }
+            ClosestObservation => {
+                let v = nf * q - 1.5;
+                // Ties go to the even 1-based order statistic, which is the
+                // lower one when the 0-based index is odd.
+                let odd = v.floor().rem_euclid(2.0) == 1.0;
+                let g = if v.fract() == 0.0 && odd { 0.0 } else { 1.0 };
+                (v.floor(), g)
+            }
+            InterpolatedInvertedCdf => continuous(nf, q, 0.0, 1.0),
+            Hazen => continuous(nf, q, 0.5, 0.5),
+            Weibull => continuous(nf, q, 0.0, 0.0),
+            Linear => continuous(nf, q, 1.0, 1.0),
+            MedianUnbiased => continuous(nf, q, 1.0 / 3.0, 1.0 / 3.0),
+            NormalUnbiased => continuous(nf, q, 3.0 / 8.0, 3.0 / 8.0),
+            Lower => (((nf - 1.0) * q).floor(), 0.0),
+            Higher => (((nf - 1.0) * q).ceil(), 0.0),
+            Midpoint => {
+                let v = (nf - 1.0) * q;
+                let g = if v.fract() == 0.0 { 0.0 } else { 0.5 };
+                (v.floor(), g)
+            }
+            Nearest => (((nf - 1.0) * q).round_ties_even(), 0.0),
+        };
+        if index < 0.0 {
+            (0, 0.0)
+        } else if index >= nf - 1.0 {
+            (n - 1, 0.0)
+        } else {
+            (index as usize, g)
+        }
+    }
+}
+
+/// Returns the floor and fraction of the virtual index for the
+/// continuous methods with plotting position parameters `alpha`, `beta`.
+fn continuous(n: f64, q: f64, alpha: f64, beta: f64) -> (f64, f64) {
+    let v = n * q + alpha + q * (1.0 - alpha - beta) - 1.0;
+    (v.floor(), v - v.floor())
+}
+
+/// Histogram bins: either a number of equal-width bins spanning the
+/// data range, or monotonically increasing bin edges.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bins<T> {
+    Count(usize),
+    Edges(Vec<T>),
+}
+
+impl<T> From<usize> for Bins<T> {
+    fn from(n: usize) -> Self {
+        Bins::Count(n)
+    }
+}
+
+impl<T> From<Vec<T>> for Bins<T> {
+    fn from(edges: Vec<T>) -> Self {
+        Bins::Edges(edges)
+    }
+}
+
+impl<T: Clone> From<&[T]> for Bins<T> {
+    fn from(edges: &[T]) -> Self {
+        Bins::Edges(edges.to_vec())
+    }
+}
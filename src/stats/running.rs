@@ -0,0 +1,128 @@
+use crate::traits::Float;
+
+/// Streaming count, mean, variance, minimum and maximum, updated one
+/// value at a time using Welford's algorithm. Accumulators fed from
+/// separate chunks or threads can be combined with `merge`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats<T> {
+    count: usize,
+    mean: T,
+    m2: T,
+    min: T,
+    max: T,
+}
+
+impl<T> Default for RunningStats<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RunningStats<T>
+where
+    T: Float,
+{
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            mean: T::zero(),
+            m2: T::zero(),
+            min: T::nan(),
+            max: T::nan(),
+        }
+    }
+
+    /// Adds `x` to the statistics.
+    pub fn push(&mut self, x: T) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean = self.mean + delta / T::from(self.count).unwrap();
+        self.m2 = self.m2 + delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Adds the values of `a`, e.g. a chunk of an `Arr`.
+    pub fn push_slice(&mut self, a: &[T]) {
+        a.iter().for_each(|&x| self.push(x));
+    }
+
+    /// Combines the statistics of `other` into `self`, as if all
+    /// values had been pushed to one accumulator.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let (na, nb) = (T::from(self.count).unwrap(), T::from(other.count).unwrap());
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        self.mean = self.mean + delta * nb / n;
+        self.m2 = self.m2 + other.m2 + delta * delta * na * nb / n;
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the mean, or NaN if empty.
+    pub fn mean(&self) -> T {
+        if self.count == 0 {
+            T::nan()
+        } else {
+            self.mean
+        }
+    }
+
+    /// Returns the variance with `ddof` delta degrees of freedom.
+    pub fn var(&self, ddof: usize) -> T {
+        if self.count == 0 {
+            return T::nan();
+        }
+        self.m2 / T::from(self.count.saturating_sub(ddof)).unwrap()
+    }
+
+    /// Returns the standard deviation with `ddof` delta degrees of freedom.
+    pub fn std(&self, ddof: usize) -> T {
+        self.var(ddof).sqrt()
+    }
+
+    /// Returns the minimum value ignoring NaNs, or NaN if empty.
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Returns the maximum value ignoring NaNs, or NaN if empty.
+    pub fn max(&self) -> T {
+        self.max
+    }
+}
+
+impl<T> Extend<T> for RunningStats<T>
+where
+    T: Float,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl<T> FromIterator<T> for RunningStats<T>
+where
+    T: Float,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
@@ -0,0 +1,81 @@
+use crate::arr::Arr;
+use crate::stats::{RunningStats, TDigest};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[test]
+fn test_running_stats() {
+    let mut rng = rand::thread_rng();
+    let a = Arr::with_vec(
+        (0..1000)
+            .map(|_| rng.gen_range(-5.0..5.0) + 1e6)
+            .collect::<Vec<f64>>(),
+    );
+
+    // Tolerances are relative to the magnitude of the expected value.
+    let close = |actual: f64, expected: f64| (actual - expected).abs() <= 1e-9 * expected.abs();
+
+    let stats: RunningStats<f64> = a.iter().copied().collect();
+    assert_eq!(stats.count(), 1000);
+    assert!(close(stats.mean(), a.mean()));
+    assert!(close(stats.var(1), a.var(1)));
+    assert!(close(stats.std(0), a.std(0)));
+    assert_eq!(stats.min(), a.min());
+    assert_eq!(stats.max(), a.max());
+
+    let mut merged = RunningStats::new();
+    for chunk in a.chunks(300) {
+        let mut part = RunningStats::new();
+        part.push_slice(chunk);
+        merged.merge(&part);
+    }
+    merged.merge(&RunningStats::new());
+    assert_eq!(merged.count(), 1000);
+    assert!(close(merged.mean(), stats.mean()));
+    assert!(close(merged.var(1), stats.var(1)));
+    assert_eq!((merged.min(), merged.max()), (stats.min(), stats.max()));
+
+    let empty = RunningStats::<f64>::default();
+    assert!(empty.mean().is_nan() && empty.var(0).is_nan() && empty.min().is_nan());
+}
+
+#[test]
+fn test_tdigest() {
+    let n = 100_000;
+    let mut values: Vec<f64> = (0..n).map(|i| i as f64).collect();
+    values.shuffle(&mut rand::thread_rng());
+
+    let digest: TDigest<f64> = values.iter().copied().collect();
+    assert_eq!(digest.count(), n);
+    assert_eq!((digest.min(), digest.max()), (0.0, (n - 1) as f64));
+    for q in [0.0, 0.001, 0.01, 0.25, 0.5, 0.75, 0.99, 0.999, 1.0] {
+        let expected = q * (n - 1) as f64;
+        // Within 0.5% of the range, and a 50% relative error in the
+        // tails, where the extreme quantiles are the exact min and max.
+        let tol = f64::min(0.005, 0.5 * f64::min(q, 1.0 - q)) * n as f64;
+        let actual = digest.quantile(q);
+        assert!(
+            (actual - expected).abs() <= tol,
+            "q={}: {} != {}",
+            q,
+            actual,
+            expected
+        );
+    }
+
+    let mut merged = TDigest::default();
+    for chunk in values.chunks(7_000) {
+        let mut part = TDigest::default();
+        part.push_slice(chunk);
+        merged.merge(&part);
+    }
+    assert_eq!(merged.count(), n);
+    assert!((merged.median() - (n / 2) as f64).abs() < 0.01 * n as f64);
+
+    let mut small = TDigest::new(50.0);
+    small.extend([3.0, f64::NAN, 1.0, 2.0]);
+    assert_eq!(small.count(), 3);
+    assert_eq!(small.median(), 2.0);
+    assert!(TDigest::<f64>::default().quantile(0.5).is_nan());
+}
@@ -0,0 +1,227 @@
+use crate::traits::Float;
+
+use std::f64::consts::PI;
+
+/// Default `TDigest` compression.
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid<T> {
+    mean: T,
+    weight: T,
+}
+
+/// Streaming quantile estimator using a merging t-digest (Dunning &
+/// Ertl, 2019). Values are summarised by at most about `compression`
+/// weighted centroids, which are smaller near the tails so extreme
+/// quantiles are estimated accurately. Digests of separate chunks or
+/// threads can be combined with `merge`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest<T> {
+    compression: f64,
+    centroids: Vec<Centroid<T>>,
+    buffer: Vec<Centroid<T>>,
+    min: T,
+    max: T,
+}
+
+impl<T> Default for TDigest<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl<T> TDigest<T>
+where
+    T: Float,
+{
+    /// Returns an empty digest. Larger `compression` values give more
+    /// accurate estimates using more memory.
+    pub fn new(compression: f64) -> Self {
+        assert!(compression >= 1.0, "compression must be at least 1");
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            min: T::nan(),
+            max: T::nan(),
+        }
+    }
+
+    /// Adds `x` to the digest. NaNs are ignored.
+    pub fn push(&mut self, x: T) {
+        if x.is_nan() {
+            return;
+        }
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.buffer.push(Centroid {
+            mean: x,
+            weight: T::one(),
+        });
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+    }
+
+    /// Adds the values of `a`, e.g. a chunk of an `Arr`.
+    pub fn push_slice(&mut self, a: &[T]) {
+        a.iter().for_each(|&x| self.push(x));
+    }
+
+    /// Combines the values summarised by `other` into `self`.
+    pub fn merge(&mut self, other: &Self) {
+        self.buffer.extend(&other.centroids);
+        self.buffer.extend(&other.buffer);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    /// Returns the number of values added.
+    pub fn count(&self) -> usize {
+        let w = self.centroids.iter().chain(&self.buffer);
+        w.fold(T::zero(), |s, c| s + c.weight).to_usize().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.buffer.is_empty()
+    }
+
+    /// Returns the minimum value, or NaN if empty.
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Returns the maximum value, or NaN if empty.
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /// Returns the estimated median, or NaN if empty.
+    pub fn median(&self) -> T {
+        self.quantile(0.5)
+    }
+
+    /// Returns the estimated quantile `q`, for `q` in `[0, 1]`,
+    /// or NaN if empty.
+    pub fn quantile(&self, q: f64) -> T {
+        assert!((0.0..=1.0).contains(&q), "quantile {} not in [0, 1]", q);
+        let merged;
+        let centroids = if self.buffer.is_empty() {
+            &self.centroids
+        } else {
+            let mut all = self.centroids.clone();
+            all.extend(&self.buffer);
+            merged = self.merge_centroids(all);
+            &merged
+        };
+        if centroids.is_empty() {
+            return T::nan();
+        }
+
+        let total = centroids.iter().fold(T::zero(), |s, c| s + c.weight);
+        let rank = T::from(q).unwrap() * total;
+        let half = T::from(0.5).unwrap();
+
+        // Each centroid's mass is centred on its mean; interpolate
+        // linearly between neighbouring centres, and between the outer
+        // centres and the extreme values.
+        let first = centroids[0];
+        if rank < first.weight * half {
+            let t = rank / (first.weight * half);
+            return self.min + (first.mean - self.min) * t;
+        }
+        let mut cum = T::zero();
+        for w in centroids.windows(2) {
+            let lo = cum + w[0].weight * half;
+            let hi = cum + w[0].weight + w[1].weight * half;
+            if rank <= hi {
+                let t = (rank - lo) / (hi - lo);
+                return w[0].mean + (w[1].mean - w[0].mean) * t;
+            }
+            cum = cum + w[0].weight;
+        }
+        let last = centroids[centroids.len() - 1];
+        let lo = total - last.weight * half;
+        let t = (rank - lo) / (last.weight * half);
+        last.mean + (self.max - last.mean) * t
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        (self.compression as usize).max(1) * 5
+    }
+
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut all = std::mem::take(&mut self.centroids);
+        all.append(&mut self.buffer);
+        self.centroids = self.merge_centroids(all);
+    }
+
+    // Sorts the centroids and merges neighbours while the merged
+    // centroid spans at most one unit of the k1 scale function
+    // `k(q) = compression / (2 pi) * asin(2q - 1)`.
+    fn merge_centroids(&self, mut all: Vec<Centroid<T>>) -> Vec<Centroid<T>> {
+        all.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+        let total = all.iter().fold(0.0, |s, c| s + c.weight.to_f64().unwrap());
+
+        let d = self.compression;
+        let k = |q: f64| d / (2.0 * PI) * (2.0 * q - 1.0).asin();
+        let q_limit = |k: f64| {
+            if k >= d / 4.0 {
+                1.0
+            } else {
+                ((k * 2.0 * PI / d).sin() + 1.0) / 2.0
+            }
+        };
+
+        let mut merged = Vec::with_capacity(d as usize);
+        let mut iter = all.into_iter();
+        let Some(mut cur) = iter.next() else {
+            return merged;
+        };
+        let mut w_so_far = 0.0;
+        let mut w_limit = total * q_limit(k(0.0) + 1.0);
+        for c in iter {
+            let proposed = (cur.weight + c.weight).to_f64().unwrap();
+            if w_so_far + proposed <= w_limit {
+                let weight = cur.weight + c.weight;
+                cur.mean = cur.mean + (c.mean - cur.mean) * c.weight / weight;
+                cur.weight = weight;
+            } else {
+                w_so_far += cur.weight.to_f64().unwrap();
+                w_limit = total * q_limit(k(w_so_far / total) + 1.0);
+                merged.push(cur);
+                cur = c;
+            }
+        }
+        merged.push(cur);
+        merged
+    }
+}
+
+impl<T> Extend<T> for TDigest<T>
+where
+    T: Float,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl<T> FromIterator<T> for TDigest<T>
+where
+    T: Float,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut digest = Self::default();
+        digest.extend(iter);
+        digest
+    }
+}
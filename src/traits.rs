@@ -307,3 +307,67 @@ impl<F: Float> ArcCos for num_complex::Complex<F> {
         num_complex::Complex::acos(*self)
     }
 }
+
+// Sample //
+
+/// A real or complex signal value that can be transformed via
+/// the complex FFT.
+pub trait Sample<F>:
+    Copy
+    + num_traits::Zero
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+{
+    fn to_complex(self) -> num_complex::Complex<F>;
+    /// Returns the real part for real types.
+    fn from_complex(c: num_complex::Complex<F>) -> Self;
+    fn conj(self) -> Self;
+    /// Multiplies by the real factor `k`.
+    fn scale(self, k: F) -> Self;
+}
+
+impl Sample<f64> for f64 {
+    fn to_complex(self) -> num_complex::Complex<f64> {
+        num_complex::Complex::new(self, 0.0)
+    }
+    fn from_complex(c: num_complex::Complex<f64>) -> Self {
+        c.re
+    }
+    fn conj(self) -> Self {
+        self
+    }
+    fn scale(self, k: f64) -> Self {
+        self * k
+    }
+}
+
+impl Sample<f32> for f32 {
+    fn to_complex(self) -> num_complex::Complex<f32> {
+        num_complex::Complex::new(self, 0.0)
+    }
+    fn from_complex(c: num_complex::Complex<f32>) -> Self {
+        c.re
+    }
+    fn conj(self) -> Self {
+        self
+    }
+    fn scale(self, k: f32) -> Self {
+        self * k
+    }
+}
+
+impl<F: Float> Sample<F> for num_complex::Complex<F> {
+    fn to_complex(self) -> num_complex::Complex<F> {
+        self
+    }
+    fn from_complex(c: num_complex::Complex<F>) -> Self {
+        c
+    }
+    fn conj(self) -> Self {
+        num_complex::Complex::conj(&self)
+    }
+    fn scale(self, k: F) -> Self {
+        num_complex::Complex::scale(&self, k)
+    }
+}